@@ -8,10 +8,36 @@ pub enum TorrentsAction {
     AddMagnet,
     MoveTorrent,
     Pause,
+    StartAll,
+    StartNow,
+    StartNowAll,
+    ToggleUpload,
+    PauseAll,
     DeleteWithFiles,
     DeleteWithoutFiles,
     ShowFiles,
     ShowStats,
+    ShowDetails,
+    QuickJump,
+    OpenDownloadDir,
+    ShowSessionSettings,
+    ShowSessionInfo,
+    ShowColumns,
+    ShowTrackerGroups,
+    ShowAltSpeedSchedule,
+    CopyHash,
+    ToggleTotals,
+    ResetView,
+    ToggleRowLayout,
+    CycleLabelFilter,
+    CyclePriority,
+    SortNextColumn,
+    ReverseSort,
+    NextSortPreset,
+    UndoRemoval,
+    VerifyAll,
+    LabelAll,
+    Export,
 }
 
 impl UserAction for TorrentsAction {
@@ -20,10 +46,36 @@ impl UserAction for TorrentsAction {
             TorrentsAction::AddMagnet => "add a magnet",
             TorrentsAction::MoveTorrent => "move torrent download directory",
             TorrentsAction::Pause => "pause/unpause",
+            TorrentsAction::StartAll => "start all visible torrents",
+            TorrentsAction::StartNow => "force start, skipping the queue",
+            TorrentsAction::StartNowAll => "force start all visible queued torrents",
+            TorrentsAction::ToggleUpload => "pause/resume uploading only (keeps downloading)",
+            TorrentsAction::PauseAll => "pause all visible torrents",
             TorrentsAction::DeleteWithFiles => "delete with files",
             TorrentsAction::DeleteWithoutFiles => "delete without files",
             TorrentsAction::ShowFiles => "show files",
             TorrentsAction::ShowStats => "show statistics",
+            TorrentsAction::ShowDetails => "show torrent details",
+            TorrentsAction::QuickJump => "jump to a torrent by its name",
+            TorrentsAction::OpenDownloadDir => "open torrent's folder in file manager",
+            TorrentsAction::ShowSessionSettings => "show session settings",
+            TorrentsAction::ShowSessionInfo => "show server info",
+            TorrentsAction::ShowColumns => "choose columns",
+            TorrentsAction::ShowTrackerGroups => "group by tracker",
+            TorrentsAction::ShowAltSpeedSchedule => "schedule alternative speeds",
+            TorrentsAction::CopyHash => "copy info hash",
+            TorrentsAction::ToggleTotals => "switch session/all-time totals",
+            TorrentsAction::ResetView => "clear filters and go to the top",
+            TorrentsAction::ToggleRowLayout => "switch compact/detailed rows",
+            TorrentsAction::CycleLabelFilter => "filter by the next label",
+            TorrentsAction::CyclePriority => "cycle bandwidth priority",
+            TorrentsAction::SortNextColumn => "sort by the next column",
+            TorrentsAction::ReverseSort => "reverse sort order",
+            TorrentsAction::NextSortPreset => "switch to the next sort preset",
+            TorrentsAction::UndoRemoval => "undo the last removal (without files)",
+            TorrentsAction::VerifyAll => "verify all visible torrents",
+            TorrentsAction::LabelAll => "add a label to all visible torrents",
+            TorrentsAction::Export => "export visible torrents to CSV/JSON",
         }
     }
 }
@@ -34,10 +86,36 @@ impl From<TorrentsAction> for Action {
             TorrentsAction::AddMagnet => Action::AddMagnet,
             TorrentsAction::MoveTorrent => Action::MoveTorrent,
             TorrentsAction::Pause => Action::Pause,
+            TorrentsAction::StartAll => Action::StartAll,
+            TorrentsAction::StartNow => Action::StartNow,
+            TorrentsAction::StartNowAll => Action::StartNowAll,
+            TorrentsAction::ToggleUpload => Action::ToggleUpload,
+            TorrentsAction::PauseAll => Action::PauseAll,
             TorrentsAction::DeleteWithFiles => Action::DeleteWithFiles,
             TorrentsAction::DeleteWithoutFiles => Action::DeleteWithoutFiles,
             TorrentsAction::ShowFiles => Action::ShowFiles,
             TorrentsAction::ShowStats => Action::ShowStats,
+            TorrentsAction::ShowDetails => Action::ShowDetails,
+            TorrentsAction::QuickJump => Action::QuickJump,
+            TorrentsAction::OpenDownloadDir => Action::OpenDownloadDir,
+            TorrentsAction::ShowSessionSettings => Action::ShowSessionSettings,
+            TorrentsAction::ShowSessionInfo => Action::ShowSessionInfo,
+            TorrentsAction::ShowColumns => Action::ShowColumns,
+            TorrentsAction::ShowTrackerGroups => Action::ShowTrackerGroups,
+            TorrentsAction::ShowAltSpeedSchedule => Action::ShowAltSpeedSchedule,
+            TorrentsAction::CopyHash => Action::CopyHash,
+            TorrentsAction::ToggleTotals => Action::ToggleTotals,
+            TorrentsAction::ResetView => Action::ResetView,
+            TorrentsAction::ToggleRowLayout => Action::ToggleRowLayout,
+            TorrentsAction::CycleLabelFilter => Action::CycleLabelFilter,
+            TorrentsAction::CyclePriority => Action::CyclePriority,
+            TorrentsAction::SortNextColumn => Action::SortNextColumn,
+            TorrentsAction::ReverseSort => Action::ReverseSort,
+            TorrentsAction::NextSortPreset => Action::NextSortPreset,
+            TorrentsAction::UndoRemoval => Action::UndoRemoval,
+            TorrentsAction::VerifyAll => Action::VerifyAll,
+            TorrentsAction::LabelAll => Action::LabelAll,
+            TorrentsAction::Export => Action::Export,
         }
     }
 }
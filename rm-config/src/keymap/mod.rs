@@ -229,8 +229,9 @@ impl<'de, T: Into<Action> + Deserialize<'de>> Deserialize<'de> for Keybinding<T>
     }
 }
 
-#[derive(Serialize, Deserialize, Hash, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Hash, Clone, Copy, PartialEq, Eq, Default)]
 pub enum KeyModifier {
+    #[default]
     None,
     Ctrl,
     Shift,
@@ -269,12 +270,6 @@ impl From<KeyModifier> for CrosstermKeyModifiers {
     }
 }
 
-impl Default for KeyModifier {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl KeymapConfig {
     pub const FILENAME: &'static str = "keymap.toml";
     const DEFAULT_CONFIG: &'static str = include_str!("../../defaults/keymap.toml");
@@ -1,5 +1,7 @@
 pub mod keymap;
 pub mod main_config;
+pub mod state;
+pub mod theme;
 mod utils;
 
 use std::{path::PathBuf, sync::LazyLock};
@@ -20,6 +22,8 @@ pub struct Config {
     pub connection: main_config::Connection,
     pub torrents_tab: main_config::TorrentsTab,
     pub search_tab: main_config::SearchTab,
+    pub theme: theme::Theme,
+    pub rss_feeds: Vec<main_config::RssFeed>,
     pub keybindings: KeymapConfig,
     pub directories: Directories,
 }
@@ -33,6 +37,7 @@ impl Config {
     fn init() -> Result<Self> {
         let main_config = MainConfig::init()?;
         let keybindings = KeymapConfig::init()?;
+        let theme = theme::Theme::new(&main_config.theme, main_config.general.accent_color);
 
         let directories = Directories {
             main_path: MainConfig::path(),
@@ -44,6 +49,8 @@ impl Config {
             connection: main_config.connection,
             torrents_tab: main_config.torrents_tab,
             search_tab: main_config.search_tab,
+            theme,
+            rss_feeds: main_config.rss_feeds,
             keybindings: keybindings.clone(),
             directories,
         })
@@ -3,11 +3,18 @@ use std::{io::ErrorKind, path::PathBuf, sync::OnceLock};
 use anyhow::{Context, Result};
 use magnetease::WhichProvider;
 use ratatui::style::Color;
-use rm_shared::header::Header;
-use serde::Deserialize;
+use rm_shared::{
+    header::Header,
+    sort::SortKey,
+    utils::{CaseMatching, NameTruncation, RowLayout, UnitSystem},
+};
+use serde::{de, Deserialize, Deserializer};
 use url::Url;
 
-use crate::utils::{self, ConfigFetchingError};
+use crate::{
+    theme::ThemeConfig,
+    utils::{self, ConfigFetchingError},
+};
 
 #[derive(Deserialize)]
 pub struct MainConfig {
@@ -17,6 +24,10 @@ pub struct MainConfig {
     pub torrents_tab: TorrentsTab,
     #[serde(default)]
     pub search_tab: SearchTab,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub rss_feeds: Vec<RssFeed>,
 }
 
 #[derive(Deserialize)]
@@ -29,6 +40,12 @@ pub struct General {
     pub beginner_mode: bool,
     #[serde(default)]
     pub headers_hide: bool,
+    #[serde(default)]
+    pub units: UnitSystem,
+    #[serde(default)]
+    pub confirm_quit: bool,
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
 }
 
 fn default_accent_color() -> Color {
@@ -39,27 +56,124 @@ fn default_beginner_mode() -> bool {
     true
 }
 
+fn default_mouse() -> bool {
+    true
+}
+
 #[derive(Deserialize)]
 pub struct Connection {
     pub username: Option<String>,
     pub password: Option<String>,
     pub url: Url,
+    // Replaces the path of the url, for servers behind a proxy that remaps it
+    #[serde(default, deserialize_with = "deserialize_rpc_path")]
+    pub rpc_path: Option<String>,
     #[serde(default = "default_refresh")]
     pub torrents_refresh: u64,
     #[serde(default = "default_refresh")]
     pub stats_refresh: u64,
     #[serde(default = "default_refresh")]
     pub free_space_refresh: u64,
+    // PEM or DER certificate to trust when connecting over HTTPS
+    pub ca_cert: Option<PathBuf>,
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    // Named download directories on this server, picked with Alt+1..9 when adding
+    #[serde(default)]
+    pub dir_shortcuts: Vec<DirShortcut>,
+}
+
+#[derive(Deserialize)]
+pub struct DirShortcut {
+    pub name: String,
+    pub path: String,
 }
 
 fn default_refresh() -> u64 {
     5
 }
 
+fn deserialize_rpc_path<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let path = String::deserialize(deserializer)?;
+    if !path.starts_with('/') {
+        return Err(de::Error::custom(format!(
+            "rpc_path has to start with a \"/\", e.g. \"/{path}\""
+        )));
+    }
+    Ok(Some(path))
+}
+
+impl Connection {
+    fn apply_rpc_path(&mut self) {
+        if let Some(path) = &self.rpc_path {
+            self.url.set_path(path);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RssFeed {
+    pub url: Url,
+    // Regex that the item's title has to match for it to be added
+    pub filter: Option<String>,
+    pub download_dir: Option<String>,
+    #[serde(default = "default_rss_refresh")]
+    pub refresh: u64,
+}
+
+fn default_rss_refresh() -> u64 {
+    900
+}
+
 #[derive(Deserialize)]
 pub struct TorrentsTab {
     #[serde(default = "default_headers")]
     pub headers: Vec<Header>,
+    #[serde(default)]
+    pub open_in_file_manager: bool,
+    #[serde(default = "default_show_speed_graph")]
+    pub show_speed_graph: bool,
+    #[serde(default = "default_speed_graph_samples")]
+    pub speed_graph_samples: usize,
+    #[serde(default = "default_show_selection_summary")]
+    pub show_selection_summary: bool,
+    #[serde(default)]
+    pub filter_case: CaseMatching,
+    #[serde(default = "default_filter_threshold")]
+    pub filter_threshold: i64,
+    #[serde(default)]
+    pub name_truncation: NameTruncation,
+    #[serde(default)]
+    pub row_layout: RowLayout,
+    // How long torrents that just finished stay highlighted, 0 turns it off
+    #[serde(default = "default_completed_highlight_secs")]
+    pub completed_highlight_secs: u64,
+    // Sort used until one is picked at runtime
+    pub default_sort: Option<SortKey>,
+    // Where the .torrent file browser starts, the current directory if unset
+    pub torrent_files_dir: Option<PathBuf>,
+}
+
+fn default_show_speed_graph() -> bool {
+    true
+}
+
+fn default_speed_graph_samples() -> usize {
+    20
+}
+
+fn default_filter_threshold() -> i64 {
+    16
+}
+
+fn default_completed_highlight_secs() -> u64 {
+    5
+}
+
+fn default_show_selection_summary() -> bool {
+    true
 }
 
 fn default_headers() -> Vec<Header> {
@@ -77,23 +191,42 @@ impl Default for TorrentsTab {
     fn default() -> Self {
         Self {
             headers: default_headers(),
+            open_in_file_manager: false,
+            show_speed_graph: default_show_speed_graph(),
+            speed_graph_samples: default_speed_graph_samples(),
+            show_selection_summary: default_show_selection_summary(),
+            filter_case: CaseMatching::default(),
+            filter_threshold: default_filter_threshold(),
+            name_truncation: NameTruncation::default(),
+            row_layout: RowLayout::default(),
+            completed_highlight_secs: default_completed_highlight_secs(),
+            default_sort: None,
+            torrent_files_dir: None,
         }
     }
 }
 
 #[derive(Deserialize)]
 pub struct SearchTab {
+    #[serde(default = "default_providers")]
     pub providers: Vec<WhichProvider>,
+    #[serde(default = "default_low_seeders_threshold")]
+    pub low_seeders_threshold: u32,
 }
 
 fn default_providers() -> Vec<WhichProvider> {
     vec![WhichProvider::Knaben, WhichProvider::Nyaa]
 }
 
+fn default_low_seeders_threshold() -> u32 {
+    3
+}
+
 impl Default for SearchTab {
     fn default() -> Self {
         Self {
             providers: default_providers(),
+            low_seeders_threshold: default_low_seeders_threshold(),
         }
     }
 }
@@ -104,7 +237,10 @@ impl MainConfig {
 
     pub(crate) fn init() -> Result<Self> {
         match utils::fetch_config::<Self>(Self::FILENAME) {
-            Ok(config) => Ok(config),
+            Ok(mut config) => {
+                config.connection.apply_rpc_path();
+                Ok(config)
+            }
             Err(e) => match e {
                 ConfigFetchingError::Io(e) if e.kind() == ErrorKind::NotFound => {
                     utils::put_config::<Self>(Self::DEFAULT_CONFIG, Self::FILENAME)?;
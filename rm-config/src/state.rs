@@ -0,0 +1,171 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    sync::{Mutex, PoisonError},
+};
+
+use anyhow::{Context, Result};
+use rm_shared::{header::Header, sort::SortKey, utils::CaseMatching};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::xdg_dirs;
+
+/// Things rustmission remembers between runs. Unlike the config, it's
+/// written by rustmission itself, so it lives in XDG_STATE_HOME.
+#[derive(Serialize, Deserialize, Default)]
+pub struct State {
+    #[serde(default)]
+    pub last_add_dir: Option<String>,
+    // GUIDs (or links) of RSS items that were already added, oldest first
+    #[serde(default)]
+    pub rss_seen: Vec<String>,
+    // URLs of RSS feeds whose items from before the first fetch were marked as seen
+    #[serde(default)]
+    pub rss_seeded_feeds: Vec<String>,
+    // Recently used torrent filters, oldest first
+    #[serde(default)]
+    pub filter_history: Vec<String>,
+    // Torrents tab columns picked at runtime, overriding the config's headers
+    #[serde(default)]
+    pub columns: Option<Vec<Header>>,
+    // Index of the tab that was open last
+    #[serde(default)]
+    pub active_tab: u8,
+    #[serde(default)]
+    pub case_matching: Option<CaseMatching>,
+    #[serde(default)]
+    pub sort: Option<SortKey>,
+    // Upload limits torrents had before their uploading got paused, oldest first
+    #[serde(default)]
+    pub paused_uploads: Vec<PausedUpload>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PausedUpload {
+    pub hash: String,
+    pub upload_limit: i32,
+    pub upload_limited: bool,
+}
+
+// Held across every read-modify-write, so two of them can't lose each other's changes
+static LOCK: Mutex<()> = Mutex::new(());
+
+impl State {
+    const FILENAME: &'static str = "state.toml";
+    const RSS_SEEN_LIMIT: usize = 1000;
+    const FILTER_HISTORY_LIMIT: usize = 50;
+    const PAUSED_UPLOADS_LIMIT: usize = 1000;
+
+    /// Falls back to the defaults when the state can't be read.
+    pub fn load() -> Self {
+        Self::try_load().unwrap_or_default()
+    }
+
+    fn try_load() -> Result<Self> {
+        let Some(path) = xdg_dirs().find_state_file(Self::FILENAME) else {
+            return Ok(Self::default());
+        };
+        let content = fs::read_to_string(&path)?;
+        toml::from_str(&content)
+            .with_context(|| format!("{} is malformed, not overwriting it", path.display()))
+    }
+
+    // Writes to a temporary file first, so a crash can't leave a truncated state behind
+    fn save(&self) -> Result<()> {
+        let path = xdg_dirs().place_state_file(Self::FILENAME)?;
+        let tmp_path = path.with_extension("toml.tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(toml::to_string(self)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    // Remembering is best effort, failing at it isn't worth bothering the user with.
+    // A state that failed to parse is left alone instead of being replaced with defaults.
+    fn update(f: impl FnOnce(&mut Self)) {
+        let _guard = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let Ok(mut state) = Self::try_load() else {
+            return;
+        };
+        f(&mut state);
+        let _ = state.save();
+    }
+
+    pub fn remember_add_dir(dir: String) {
+        Self::update(|state| state.last_add_dir = Some(dir))
+    }
+
+    pub fn remember_filter(filter: String) {
+        Self::update(|state| {
+            state
+                .filter_history
+                .retain(|old_filter| *old_filter != filter);
+            state.filter_history.push(filter);
+            let overflow = state
+                .filter_history
+                .len()
+                .saturating_sub(Self::FILTER_HISTORY_LIMIT);
+            state.filter_history.drain(..overflow);
+        })
+    }
+
+    pub fn remember_columns(columns: Vec<Header>) {
+        Self::update(|state| state.columns = Some(columns))
+    }
+
+    pub fn remember_active_tab(tab: u8) {
+        Self::update(|state| state.active_tab = tab)
+    }
+
+    pub fn remember_case_matching(case_matching: CaseMatching) {
+        Self::update(|state| state.case_matching = Some(case_matching))
+    }
+
+    pub fn remember_sort(sort: Option<SortKey>) {
+        Self::update(|state| state.sort = sort)
+    }
+
+    pub fn remember_rss_items(ids: impl IntoIterator<Item = String>) {
+        Self::update(|state| state.push_rss_items(ids))
+    }
+
+    // Marks everything a feed had when first fetched as seen
+    pub fn remember_rss_feed_seeded(url: String, ids: impl IntoIterator<Item = String>) {
+        Self::update(|state| {
+            state.push_rss_items(ids);
+            state.rss_seeded_feeds.push(url);
+        })
+    }
+
+    pub fn remember_paused_upload(paused: PausedUpload) {
+        Self::update(|state| {
+            state
+                .paused_uploads
+                .retain(|old_paused| old_paused.hash != paused.hash);
+            state.paused_uploads.push(paused);
+            let overflow = state
+                .paused_uploads
+                .len()
+                .saturating_sub(Self::PAUSED_UPLOADS_LIMIT);
+            state.paused_uploads.drain(..overflow);
+        })
+    }
+
+    // Forgets the limit a torrent had before its uploading got paused and returns it
+    pub fn take_paused_upload(hash: &str) -> Option<PausedUpload> {
+        let mut taken = None;
+        Self::update(|state| {
+            if let Some(idx) = state.paused_uploads.iter().position(|p| p.hash == hash) {
+                taken = Some(state.paused_uploads.remove(idx));
+            }
+        });
+        taken
+    }
+
+    fn push_rss_items(&mut self, ids: impl IntoIterator<Item = String>) {
+        self.rss_seen.extend(ids);
+        let overflow = self.rss_seen.len().saturating_sub(Self::RSS_SEEN_LIMIT);
+        self.rss_seen.drain(..overflow);
+    }
+}
@@ -0,0 +1,84 @@
+use ratatui::style::{Color, Style, Stylize};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default, Clone, Copy)]
+pub enum ThemePreset {
+    #[default]
+    Default,
+    Dark,
+    Gruvbox,
+}
+
+// Theme as written in the config, roles that aren't set come from the preset.
+#[derive(Deserialize, Default)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub preset: ThemePreset,
+    pub header: Option<Color>,
+    pub selected_row: Option<Color>,
+    pub downloading: Option<Color>,
+    pub seeding: Option<Color>,
+    pub paused: Option<Color>,
+    pub error: Option<Color>,
+}
+
+pub struct Theme {
+    pub header: Color,
+    pub selected_row: Color,
+    pub downloading: Color,
+    pub seeding: Color,
+    pub paused: Color,
+    pub error: Color,
+}
+
+impl Theme {
+    pub(crate) fn new(config: &ThemeConfig, accent_color: Color) -> Self {
+        let preset = Self::preset(config.preset, accent_color);
+
+        Self {
+            header: config.header.unwrap_or(preset.header),
+            selected_row: config.selected_row.unwrap_or(preset.selected_row),
+            downloading: config.downloading.unwrap_or(preset.downloading),
+            seeding: config.seeding.unwrap_or(preset.seeding),
+            paused: config.paused.unwrap_or(preset.paused),
+            error: config.error.unwrap_or(preset.error),
+        }
+    }
+
+    fn preset(preset: ThemePreset, accent_color: Color) -> Self {
+        match preset {
+            ThemePreset::Default => Self {
+                header: Color::Reset,
+                selected_row: accent_color,
+                downloading: Color::Reset,
+                seeding: Color::Reset,
+                paused: Color::DarkGray,
+                error: Color::Red,
+            },
+            ThemePreset::Dark => Self {
+                header: Color::White,
+                selected_row: accent_color,
+                downloading: Color::LightBlue,
+                seeding: Color::LightGreen,
+                paused: Color::DarkGray,
+                error: Color::LightRed,
+            },
+            ThemePreset::Gruvbox => Self {
+                header: Color::Rgb(0xeb, 0xdb, 0xb2),
+                selected_row: Color::Rgb(0xfa, 0xbd, 0x2f),
+                downloading: Color::Rgb(0x83, 0xa5, 0x98),
+                seeding: Color::Rgb(0xb8, 0xbb, 0x26),
+                paused: Color::Rgb(0x92, 0x83, 0x74),
+                error: Color::Rgb(0xfb, 0x49, 0x34),
+            },
+        }
+    }
+
+    pub fn selected_row_style(&self) -> Style {
+        Style::default().on_black().bold().fg(self.selected_row)
+    }
+
+    pub fn header_style(&self) -> Style {
+        Style::default().fg(self.header)
+    }
+}
@@ -6,9 +6,9 @@ use transmission_rpc::types::TorrentAddArgs;
 
 use crate::transmission;
 
-pub(super) async fn add_torrent(torrent: String) -> Result<()> {
-    let mut transclient = transmission::utils::new_client();
-    let args = {
+pub(super) async fn add_torrent(torrent: String, dir: Option<String>) -> Result<()> {
+    let mut transclient = transmission::utils::new_client()?;
+    let mut args = {
         if torrent.starts_with("magnet:")
             || torrent.starts_with("http:")
             || torrent.starts_with("https:")
@@ -34,6 +34,8 @@ pub(super) async fn add_torrent(torrent: String) -> Result<()> {
         }
     };
 
+    args.download_dir = dir;
+
     if let Err(e) = transclient.torrent_add(args).await {
         eprintln!("error while adding a torrent: {e}");
         if e.to_string().contains("expected value at line") {
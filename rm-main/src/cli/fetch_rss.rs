@@ -5,7 +5,7 @@ use transmission_rpc::types::TorrentAddArgs;
 use crate::transmission;
 
 pub async fn fetch_rss(url: &str, filter: Option<&str>) -> Result<()> {
-    let mut transclient = transmission::utils::new_client();
+    let mut transclient = transmission::utils::new_client()?;
     let content = reqwest::get(url).await?.bytes().await?;
     let channel = rss::Channel::read_from(&content[..])?;
     let re: Option<Regex> = {
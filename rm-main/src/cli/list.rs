@@ -0,0 +1,60 @@
+use anyhow::{anyhow, Result};
+use rm_config::{state::State, CONFIG};
+use rm_shared::header::Header;
+use serde_json::{Map, Value};
+
+use crate::transmission::{
+    self,
+    columns::{header_text, header_value, torrent_fields},
+};
+
+pub(super) async fn list(json: bool) -> Result<()> {
+    let mut transclient = transmission::utils::new_client()?;
+
+    // Same columns as the table, minus the one that's only there for spacing
+    let headers: Vec<Header> = State::load()
+        .columns
+        .unwrap_or_else(|| CONFIG.torrents_tab.headers.clone())
+        .into_iter()
+        .filter(|header| *header != Header::Padding)
+        .collect();
+
+    let fields = torrent_fields(&headers, None, false);
+    let torrents = transclient
+        .torrent_get(Some(fields), None)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch torrents: {e}"))?
+        .arguments
+        .torrents;
+
+    if json {
+        let records: Vec<Map<String, Value>> = torrents
+            .iter()
+            .map(|torrent| {
+                headers
+                    .iter()
+                    .map(|header| {
+                        let key = serde_json::to_value(header)?
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string();
+                        Ok((key, header_value(torrent, *header)))
+                    })
+                    .collect::<Result<_, serde_json::Error>>()
+            })
+            .collect::<Result<_, _>>()?;
+        println!("{}", serde_json::to_string_pretty(&records)?);
+    } else {
+        let descriptions: Vec<&str> = headers.iter().map(Header::description).collect();
+        println!("{}", descriptions.join("\t"));
+        for torrent in &torrents {
+            let cells: Vec<String> = headers
+                .iter()
+                .map(|header| header_text(torrent, *header))
+                .collect();
+            println!("{}", cells.join("\t"));
+        }
+    }
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
 mod add_torrent;
 mod fetch_rss;
+mod list;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use rm_config::CONFIG;
+
+use crate::transmission;
 
 use add_torrent::add_torrent;
 use fetch_rss::fetch_rss;
+use list::list;
 
 #[derive(Parser)]
 #[command(version, about)]
@@ -16,14 +21,37 @@ pub struct Args {
 
 #[derive(Subcommand)]
 pub enum Commands {
-    AddTorrent { torrent: String },
-    FetchRss { url: String, filter: Option<String> },
+    #[command(alias = "add")]
+    AddTorrent {
+        torrent: String,
+        /// Where to download it, instead of the server's default directory
+        #[arg(long)]
+        dir: Option<String>,
+    },
+    FetchRss {
+        url: String,
+        filter: Option<String>,
+    },
+    /// Print the torrents with the same columns as the table
+    List {
+        /// Print JSON instead of tab-separated text
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 pub async fn handle_command(command: Commands) -> Result<()> {
+    if CONFIG.connection.accept_invalid_certs {
+        eprintln!(
+            "WARNING: {}",
+            transmission::utils::INSECURE_CONNECTION_WARNING
+        );
+    }
+
     match command {
-        Commands::AddTorrent { torrent } => add_torrent(torrent).await?,
+        Commands::AddTorrent { torrent, dir } => add_torrent(torrent, dir).await?,
         Commands::FetchRss { url, filter } => fetch_rss(&url, filter.as_deref()).await?,
+        Commands::List { json } => list(json).await?,
     }
     Ok(())
 }
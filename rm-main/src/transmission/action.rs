@@ -1,26 +1,49 @@
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+use base64::Engine;
+
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot::Sender;
 use transmission_rpc::types::{
-    FreeSpace, Id, SessionGet, SessionStats, Torrent, TorrentAction as RPCAction, TorrentAddArgs,
-    TorrentGetField, TorrentSetArgs,
+    FreeSpace, Id, Priority, SessionGet, SessionSetArgs, SessionStats, Torrent,
+    TorrentAction as RPCAction, TorrentAddArgs, TorrentAddedOrDuplicate, TorrentGetField,
+    TorrentSetArgs,
 };
 use transmission_rpc::TransClient;
 
+use rm_config::state::State;
 use rm_shared::action::ErrorMessage;
 use rm_shared::action::UpdateAction;
+use rm_shared::session::{
+    AltSpeedSchedule, EffectiveLimits, SessionInfo, SessionSettings, SessionSpeedLimits,
+    TorrentSpeedLimits, TorrentUploadLimit,
+};
+
+use super::raw::RawClient;
 
 const FAILED_TO_COMMUNICATE: &str = "Failed to communicate with Transmission";
 
+pub type TorrentsWithUploadLimits = (Vec<Torrent>, Vec<TorrentUploadLimit>);
+
 pub enum TorrentAction {
     // Add a torrent with this Magnet/URL, Directory
     Add(String, Option<String>),
+    // Same as Add, but part of a batch that reports its status by itself.
+    // Tells whether the torrent is in Transmission now, if asked to.
+    AddQueued(String, Option<String>, Option<Sender<bool>>),
     // Stop Torrents with these given IDs
     Stop(Vec<Id>),
     // Start Torrents with these given IDs
     Start(Vec<Id>),
+    // Start Torrents with these given IDs right away, bypassing the queue
+    StartNow(Vec<Id>),
+    // Verify data of Torrents with these given IDs
+    Verify(Vec<Id>),
     // Torrent ID, Directory to move to
     Move(Vec<Id>, String),
     // Delete Torrents with these given IDs (without files)
@@ -29,60 +52,202 @@ pub enum TorrentAction {
     DelWithFiles(Vec<Id>),
     // Set various properties to Torrents with these given IDs
     SetArgs(Box<TorrentSetArgs>, Option<Vec<Id>>),
+    // Add trackers (announce URLs) to and remove trackers (by their IDs) from
+    // a Torrent with given ID
+    EditTrackers {
+        id: Id,
+        add: Vec<String>,
+        remove: Vec<i32>,
+    },
+    // Replace labels of a Torrent with given ID
+    SetLabels {
+        id: Id,
+        labels: Vec<String>,
+    },
+    // Set bandwidth priority of torrents with these given IDs
+    SetBandwidthPriority {
+        ids: Vec<Id>,
+        priority: Priority,
+    },
     // Get info about current Transmission session
     GetSessionGet(Sender<Result<SessionGet, Box<ErrorMessage>>>),
+    // Get global settings of current Transmission session
+    GetSessionSettings(Sender<Result<SessionSettings, Box<ErrorMessage>>>),
+    // Get info about the Transmission daemon itself
+    GetSessionInfo(Sender<Result<SessionInfo, Box<ErrorMessage>>>),
+    // Get the schedule of the alternative speed limits
+    GetAltSpeedSchedule(Sender<Result<AltSpeedSchedule, Box<ErrorMessage>>>),
+    // Change global settings of current Transmission session
+    SetSessionArgs(Box<SessionSetArgs>),
     // Get info about current Transmission session statistics
     GetSessionStats(Sender<Result<Arc<SessionStats>, Box<ErrorMessage>>>),
     // Get info about available space on the disk
     GetFreeSpace(String, Sender<Result<FreeSpace, Box<ErrorMessage>>>),
     // Get info about all Torrents with these given Fields.
+    // Along with their upload limits, which transmission-rpc leaves out
     GetTorrents(
         Vec<TorrentGetField>,
-        Sender<Result<Vec<Torrent>, Box<ErrorMessage>>>,
+        Sender<Result<TorrentsWithUploadLimits, Box<ErrorMessage>>>,
     ),
     // Get info about specific torrents with these given IDs
     GetTorrentsById(Vec<Id>, Sender<Result<Vec<Torrent>, Box<ErrorMessage>>>),
+    // Get the speed limits that actually apply to a Torrent with given ID
+    GetEffectiveLimits(Id, Sender<Result<EffectiveLimits, Box<ErrorMessage>>>),
+}
+
+// Changes to trackers that leave the other ones, and their tiers, as they are
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackerEdits {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tracker_add: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tracker_remove: Vec<i32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TorrentWithUploadLimit {
+    #[serde(flatten)]
+    torrent: Torrent,
+    upload_limit: i32,
+    upload_limited: bool,
+}
+
+impl TorrentWithUploadLimit {
+    const FIELDS: &'static [&'static str] = &["uploadLimit", "uploadLimited"];
+
+    fn upload_limit(&self) -> Option<TorrentUploadLimit> {
+        Some(TorrentUploadLimit {
+            id: self.torrent.id?,
+            upload_limit: self.upload_limit,
+            upload_limited: self.upload_limited,
+        })
+    }
+}
+
+// Counts the action as handled once it goes out of scope
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Returns what the status task of this add should end with, if anything
+async fn add_torrent(
+    client: &mut TransClient,
+    url: &str,
+    directory: Option<String>,
+    action_tx: &UnboundedSender<UpdateAction>,
+) -> Option<UpdateAction> {
+    let mut args =
+        if url.starts_with("magnet:") || url.starts_with("http:") || url.starts_with("https:") {
+            TorrentAddArgs {
+                filename: Some(url.to_string()),
+                ..Default::default()
+            }
+        } else if url.starts_with("www") {
+            TorrentAddArgs {
+                filename: Some(format!("https://{url}")),
+                ..Default::default()
+            }
+        } else if Path::new(url).is_file() {
+            // The daemon may be on another machine, so send the file itself
+            match fs::read(url) {
+                Ok(buf) => TorrentAddArgs {
+                    metainfo: Some(base64::engine::general_purpose::STANDARD.encode(buf)),
+                    ..Default::default()
+                },
+                Err(err) => {
+                    let msg = format!("Failed to read torrent file: \"{url}\"");
+                    let err_message =
+                        ErrorMessage::new("Failed to add torrent", msg, Box::new(err));
+                    action_tx
+                        .send(UpdateAction::Error(Box::new(err_message)))
+                        .unwrap();
+                    return Some(UpdateAction::TaskFailure);
+                }
+            }
+        } else {
+            // Not here, so it might be a path on the daemon's machine
+            TorrentAddArgs {
+                filename: Some(url.to_string()),
+                ..Default::default()
+            }
+        };
+    args.download_dir = directory.clone();
+
+    match client.torrent_add(args).await {
+        Ok(response) => match response.arguments {
+            TorrentAddedOrDuplicate::TorrentDuplicate(torrent) => {
+                action_tx
+                    .send(UpdateAction::TorrentDuplicate(Box::new(torrent)))
+                    .unwrap();
+                None
+            }
+            _ => {
+                if let Some(directory) = directory {
+                    State::remember_add_dir(directory);
+                }
+                Some(UpdateAction::TaskSuccess)
+            }
+        },
+        Err(err) => {
+            let msg = format!("Failed to add torrent with URL/Path: \"{url}\"");
+            let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+            action_tx
+                .send(UpdateAction::Error(Box::new(err_message)))
+                .unwrap();
+            Some(UpdateAction::TaskFailure)
+        }
+    }
 }
 
 pub async fn action_handler(
     mut client: TransClient,
+    mut raw_client: RawClient,
     mut trans_rx: UnboundedReceiver<TorrentAction>,
     action_tx: UnboundedSender<UpdateAction>,
+    rpc_calls: Arc<AtomicUsize>,
 ) {
+    // Results are sent with `let _ =` since whoever asked for them might not be
+    // waiting anymore, like a prefetch for a torrent that's no longer selected
     while let Some(action) = trans_rx.recv().await {
+        let _in_flight = InFlightGuard(&rpc_calls);
         match action {
-            TorrentAction::Add(ref url, directory) => {
-                let formatted = {
-                    if url.starts_with("www") {
-                        format!("https://{url}")
-                    } else {
-                        url.to_string()
-                    }
-                };
-                let args = TorrentAddArgs {
-                    filename: Some(formatted),
-                    download_dir: directory,
-                    ..Default::default()
-                };
-                match client.torrent_add(args).await {
-                    Ok(_) => {
-                        action_tx.send(UpdateAction::TaskSuccess).unwrap();
-                    }
+            TorrentAction::Add(url, directory) => {
+                if let Some(completion) =
+                    add_torrent(&mut client, &url, directory, &action_tx).await
+                {
+                    action_tx.send(completion).unwrap();
+                }
+            }
+            TorrentAction::AddQueued(url, directory, added_tx) => {
+                let completion = add_torrent(&mut client, &url, directory, &action_tx).await;
+                if let Some(added_tx) = added_tx {
+                    let failed = matches!(completion, Some(UpdateAction::TaskFailure));
+                    let _ = added_tx.send(!failed);
+                }
+            }
+            TorrentAction::Stop(ids) => {
+                match client.torrent_action(RPCAction::Stop, ids.clone()).await {
+                    Ok(_) => (),
                     Err(err) => {
-                        let msg = format!("Failed to add torrent with URL/Path: \"{url}\"");
+                        let msg = format!("Failed to stop torrents with these IDs: {:?}", ids);
                         let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
                         action_tx
                             .send(UpdateAction::Error(Box::new(err_message)))
                             .unwrap();
-                        action_tx.send(UpdateAction::TaskFailure).unwrap();
                     }
                 }
             }
-            TorrentAction::Stop(ids) => {
-                match client.torrent_action(RPCAction::Stop, ids.clone()).await {
+            TorrentAction::Verify(ids) => {
+                match client.torrent_action(RPCAction::Verify, ids.clone()).await {
                     Ok(_) => (),
                     Err(err) => {
-                        let msg = format!("Failed to stop torrents with these IDs: {:?}", ids);
+                        let msg = format!("Failed to verify torrents with these IDs: {:?}", ids);
                         let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
                         action_tx
                             .send(UpdateAction::Error(Box::new(err_message)))
@@ -102,6 +267,22 @@ pub async fn action_handler(
                     }
                 }
             }
+            TorrentAction::StartNow(ids) => {
+                match client
+                    .torrent_action(RPCAction::StartNow, ids.clone())
+                    .await
+                {
+                    Ok(_) => (),
+                    Err(err) => {
+                        let msg =
+                            format!("Failed to force-start torrents with these IDs: {:?}", ids);
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                        action_tx
+                            .send(UpdateAction::Error(Box::new(err_message)))
+                            .unwrap();
+                    }
+                }
+            }
             TorrentAction::DelWithFiles(ids) => {
                 match client.torrent_remove(ids.clone(), true).await {
                     Ok(_) => action_tx.send(UpdateAction::TaskSuccess).unwrap(),
@@ -116,8 +297,28 @@ pub async fn action_handler(
                 }
             }
             TorrentAction::DelWithoutFiles(ids) => {
+                // Remember enough to add them back, in case the user changes their mind
+                let removed_torrents = client
+                    .torrent_get(
+                        Some(vec![
+                            TorrentGetField::HashString,
+                            TorrentGetField::Name,
+                            TorrentGetField::DownloadDir,
+                            TorrentGetField::Trackers,
+                        ]),
+                        Some(ids.clone()),
+                    )
+                    .await
+                    .map(|response| response.arguments.torrents)
+                    .unwrap_or_default();
+
                 match client.torrent_remove(ids.clone(), false).await {
-                    Ok(_) => action_tx.send(UpdateAction::TaskSuccess).unwrap(),
+                    Ok(_) => {
+                        action_tx.send(UpdateAction::TaskSuccess).unwrap();
+                        action_tx
+                            .send(UpdateAction::TorrentsRemoved(removed_torrents))
+                            .unwrap();
+                    }
                     Err(err) => {
                         let msg = format!("Failed to remove torrents with these IDs: {:?}", ids);
                         let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
@@ -143,18 +344,108 @@ pub async fn action_handler(
                     }
                 }
             }
+            TorrentAction::EditTrackers { id, add, remove } => {
+                let edits = TrackerEdits {
+                    tracker_add: add,
+                    tracker_remove: remove,
+                };
+                if let Err(err) = raw_client
+                    .torrent_set(std::slice::from_ref(&id), edits)
+                    .await
+                {
+                    let msg = format!("Failed to edit trackers of torrent with ID: {:?}", id);
+                    let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                    action_tx
+                        .send(UpdateAction::Error(Box::new(err_message)))
+                        .unwrap();
+                }
+            }
+            TorrentAction::SetLabels { id, labels } => {
+                let args = TorrentSetArgs {
+                    labels: Some(labels),
+                    ..Default::default()
+                };
+                if let Err(err) = client.torrent_set(args, Some(vec![id.clone()])).await {
+                    let msg = format!("Failed to set labels of torrent with ID: {:?}", id);
+                    let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                    action_tx
+                        .send(UpdateAction::Error(Box::new(err_message)))
+                        .unwrap();
+                }
+            }
+            TorrentAction::SetBandwidthPriority { ids, priority } => {
+                let args = TorrentSetArgs {
+                    bandwidth_priority: Some(priority),
+                    ..Default::default()
+                };
+                if let Err(err) = client.torrent_set(args, Some(ids.clone())).await {
+                    let msg = format!(
+                        "Failed to set priority of torrents with these IDs: {:?}",
+                        ids
+                    );
+                    let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                    action_tx
+                        .send(UpdateAction::Error(Box::new(err_message)))
+                        .unwrap();
+                    // The new priority is already shown
+                    action_tx.send(UpdateAction::RefreshTorrents).unwrap();
+                }
+            }
             TorrentAction::GetSessionGet(sender) => match client.session_get().await {
                 Ok(session_get) => {
-                    sender.send(Ok(session_get.arguments)).unwrap();
+                    let _ = sender.send(Ok(session_get.arguments));
                 }
                 Err(err) => {
                     let msg = "Failed to get session data";
                     let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
+                    let _ = sender.send(Err(Box::new(err_message)));
+                }
+            },
+            TorrentAction::GetSessionSettings(sender) => {
+                match raw_client.session_get(SessionSettings::FIELDS).await {
+                    Ok(settings) => {
+                        let _ = sender.send(Ok(settings));
+                    }
+                    Err(err) => {
+                        let msg = "Failed to get session settings";
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                        let _ = sender.send(Err(Box::new(err_message)));
+                    }
+                }
+            }
+            TorrentAction::GetSessionInfo(sender) => {
+                match raw_client.session_get(SessionInfo::FIELDS).await {
+                    Ok(info) => {
+                        let _ = sender.send(Ok(info));
+                    }
+                    Err(err) => {
+                        let msg = "Failed to get session info";
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                        let _ = sender.send(Err(Box::new(err_message)));
+                    }
+                }
+            }
+            TorrentAction::GetAltSpeedSchedule(sender) => {
+                match raw_client.session_get(AltSpeedSchedule::FIELDS).await {
+                    Ok(schedule) => {
+                        let _ = sender.send(Ok(schedule));
+                    }
+                    Err(err) => {
+                        let msg = "Failed to get the alternative speed schedule";
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                        let _ = sender.send(Err(Box::new(err_message)));
+                    }
+                }
+            }
+            TorrentAction::SetSessionArgs(args) => {
+                if let Err(err) = client.session_set(*args).await {
+                    let msg = "Failed to change session settings";
+                    let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
                     action_tx
                         .send(UpdateAction::Error(Box::new(err_message)))
                         .unwrap();
                 }
-            },
+            }
             TorrentAction::Move(ids, new_directory) => {
                 if let Err(err) = client
                     .torrent_set_location(ids, new_directory.clone(), Some(true))
@@ -168,38 +459,82 @@ pub async fn action_handler(
                 }
             }
             TorrentAction::GetSessionStats(sender) => match client.session_stats().await {
-                Ok(stats) => sender.send(Ok(Arc::new(stats.arguments))).unwrap(),
+                Ok(stats) => {
+                    let _ = sender.send(Ok(Arc::new(stats.arguments)));
+                }
                 Err(err) => {
                     let msg = "Failed to get session stats";
                     let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
-                    sender.send(Err(Box::new(err_message))).unwrap();
+                    let _ = sender.send(Err(Box::new(err_message)));
                 }
             },
             TorrentAction::GetFreeSpace(path, sender) => match client.free_space(path).await {
-                Ok(free_space) => sender.send(Ok(free_space.arguments)).unwrap(),
+                Ok(free_space) => {
+                    let _ = sender.send(Ok(free_space.arguments));
+                }
                 Err(err) => {
                     let msg = "Failed to get free space info";
                     let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
-                    sender.send(Err(Box::new(err_message))).unwrap();
+                    let _ = sender.send(Err(Box::new(err_message)));
                 }
             },
             TorrentAction::GetTorrents(fields, sender) => {
-                match client.torrent_get(Some(fields), None).await {
-                    Ok(torrents) => sender.send(Ok(torrents.arguments.torrents)).unwrap(),
+                let fields: Vec<String> = fields.iter().map(TorrentGetField::to_str).collect();
+                let fields: Vec<&str> = fields
+                    .iter()
+                    .map(String::as_str)
+                    .chain(TorrentWithUploadLimit::FIELDS.iter().copied())
+                    .collect();
+                match raw_client
+                    .torrent_get::<TorrentWithUploadLimit>(None, &fields)
+                    .await
+                {
+                    Ok(torrents) => {
+                        let limits = torrents
+                            .iter()
+                            .filter_map(TorrentWithUploadLimit::upload_limit)
+                            .collect();
+                        let torrents = torrents.into_iter().map(|t| t.torrent).collect();
+                        let _ = sender.send(Ok((torrents, limits)));
+                    }
                     Err(err) => {
                         let msg = "Failed to fetch torrent data";
-                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
-                        sender.send(Err(Box::new(err_message))).unwrap();
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                        let _ = sender.send(Err(Box::new(err_message)));
                     }
                 }
             }
             TorrentAction::GetTorrentsById(ids, sender) => {
                 match client.torrent_get(None, Some(ids.clone())).await {
-                    Ok(torrents) => sender.send(Ok(torrents.arguments.torrents)).unwrap(),
+                    Ok(torrents) => {
+                        let _ = sender.send(Ok(torrents.arguments.torrents));
+                    }
                     Err(err) => {
                         let msg = format!("Failed to fetch torrents with these IDs: {:?}", ids);
                         let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err);
-                        sender.send(Err(Box::new(err_message))).unwrap();
+                        let _ = sender.send(Err(Box::new(err_message)));
+                    }
+                }
+            }
+            TorrentAction::GetEffectiveLimits(id, sender) => {
+                let session_limits = raw_client
+                    .session_get::<SessionSpeedLimits>(SessionSpeedLimits::FIELDS)
+                    .await;
+                let torrent_limits = raw_client
+                    .torrent_get::<TorrentSpeedLimits>(Some(&[id]), TorrentSpeedLimits::FIELDS)
+                    .await;
+
+                match (session_limits, torrent_limits) {
+                    (Ok(session_limits), Ok(mut torrent_limits)) => {
+                        if let Some(torrent_limits) = torrent_limits.pop() {
+                            let limits = EffectiveLimits::new(&session_limits, &torrent_limits);
+                            let _ = sender.send(Ok(limits));
+                        }
+                    }
+                    (Err(err), _) | (_, Err(err)) => {
+                        let msg = "Failed to get speed limits";
+                        let err_message = ErrorMessage::new(FAILED_TO_COMMUNICATE, msg, err.into());
+                        let _ = sender.send(Err(Box::new(err_message)));
                     }
                 }
             }
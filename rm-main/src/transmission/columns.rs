@@ -0,0 +1,134 @@
+use rm_config::CONFIG;
+use rm_shared::{
+    header::Header,
+    utils::{eta_format, seconds_to_relative_format},
+};
+use serde_json::{json, Value};
+use transmission_rpc::types::{Torrent, TorrentGetField, TorrentStatus};
+
+// Fields the table always needs: for the name and its indicators, the row style,
+// the selection summary, the label filter and the export.
+const BASE_FIELDS: [TorrentGetField; 15] = [
+    TorrentGetField::Id,
+    TorrentGetField::HashString,
+    TorrentGetField::Name,
+    TorrentGetField::Status,
+    TorrentGetField::Error,
+    TorrentGetField::ErrorString,
+    TorrentGetField::BandwidthPriority,
+    TorrentGetField::Labels,
+    TorrentGetField::DownloadDir,
+    TorrentGetField::SizeWhenDone,
+    TorrentGetField::PercentDone,
+    TorrentGetField::RateDownload,
+    TorrentGetField::RateUpload,
+    TorrentGetField::UploadRatio,
+    TorrentGetField::PeersConnected,
+];
+
+// Fields to fetch for the table, given the columns it shows and the one it's sorted by.
+// Trackers are big, so they're only fetched while torrents are grouped by them.
+pub fn torrent_fields(
+    headers: &[Header],
+    sort_header: Option<Header>,
+    with_trackers: bool,
+) -> Vec<TorrentGetField> {
+    let mut fields = BASE_FIELDS.to_vec();
+    for header in headers.iter().chain(&sort_header) {
+        let header_fields: &[TorrentGetField] = match header {
+            Header::Eta => &[TorrentGetField::Eta, TorrentGetField::IsStalled],
+            Header::UploadedEver => &[TorrentGetField::UploadedEver],
+            Header::ActivityDate => &[TorrentGetField::ActivityDate],
+            Header::AddedDate => &[TorrentGetField::AddedDate],
+            Header::DoneDate => &[TorrentGetField::DoneDate],
+            Header::PeersSendingToUs => &[TorrentGetField::PeersSendingToUs],
+            Header::PeersGettingFromUs => &[TorrentGetField::PeersGettingFromUs],
+            _ => &[],
+        };
+        for field in header_fields {
+            if !fields.iter().any(|f| f.to_str() == field.to_str()) {
+                fields.push(*field);
+            }
+        }
+    }
+    if with_trackers {
+        fields.push(TorrentGetField::Trackers);
+    }
+    fields
+}
+
+// A column as it comes from Transmission: bytes, bytes per second, a fraction for
+// the progress, seconds for the ETA and UNIX timestamps for dates.
+// Null when Transmission didn't send it.
+pub fn header_value(torrent: &Torrent, header: Header) -> Value {
+    match header {
+        Header::Name => json!(torrent.name),
+        Header::SizeWhenDone => json!(torrent.size_when_done),
+        Header::Progress => json!(torrent.percent_done),
+        Header::Eta => json!(torrent.eta),
+        Header::DownloadRate => json!(torrent.rate_download),
+        Header::UploadRate => json!(torrent.rate_upload),
+        Header::DownloadDir => json!(torrent.download_dir),
+        Header::Padding => Value::Null,
+        Header::UploadRatio => json!(torrent.upload_ratio),
+        Header::UploadedEver => json!(torrent.uploaded_ever),
+        Header::Id => json!(torrent.id),
+        Header::ActivityDate => json!(torrent.activity_date),
+        Header::AddedDate => json!(torrent.added_date),
+        // 0 means that the torrent isn't done yet
+        Header::DoneDate => json!(torrent.done_date.filter(|date| *date != 0)),
+        Header::PeersConnected => json!(torrent.peers_connected),
+        Header::PeersSendingToUs => json!(torrent.peers_sending_to_us),
+        Header::PeersGettingFromUs => json!(torrent.peers_getting_from_us),
+        Header::SmallStatus => json!(torrent.status.map(|status| format!("{status:?}"))),
+    }
+}
+
+// A column as plain text, formatted like in the table but without glyphs.
+// "?" when Transmission didn't send what it needs.
+pub fn header_text(torrent: &Torrent, header: Header) -> String {
+    let units = CONFIG.general.units;
+    let text = match header {
+        Header::Name => torrent.name.clone(),
+        Header::SizeWhenDone => torrent.size_when_done.map(|size| units.format_size(size)),
+        Header::Progress => torrent
+            .percent_done
+            .map(|percent| format!("{:.2}%", percent * 100f32)),
+        Header::Eta => torrent.eta.map(|eta| {
+            let is_stalled = torrent.status == Some(TorrentStatus::Downloading)
+                && torrent.is_stalled.unwrap_or_default();
+            eta_format(eta, is_stalled)
+        }),
+        Header::DownloadRate => torrent.rate_download.map(|speed| units.format_speed(speed)),
+        Header::UploadRate => torrent.rate_upload.map(|speed| units.format_speed(speed)),
+        Header::DownloadDir => torrent.download_dir.clone(),
+        Header::Padding => Some(String::new()),
+        Header::UploadRatio => torrent.upload_ratio.map(|ratio| format!("{ratio:.1}")),
+        Header::UploadedEver => torrent.uploaded_ever.map(|size| units.format_size(size)),
+        Header::Id => torrent.id.map(|id| id.to_string()),
+        Header::ActivityDate => torrent.activity_date.map(|date| {
+            chrono::DateTime::from_timestamp(date, 0)
+                .unwrap_or_default()
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        }),
+        Header::AddedDate => torrent.added_date.map(relative_time_text),
+        Header::DoneDate => torrent.done_date.map(|date| {
+            if date == 0 {
+                String::new()
+            } else {
+                relative_time_text(date)
+            }
+        }),
+        Header::PeersConnected => torrent.peers_connected.map(|peers| peers.to_string()),
+        Header::PeersSendingToUs => torrent.peers_sending_to_us.map(|peers| peers.to_string()),
+        Header::PeersGettingFromUs => torrent.peers_getting_from_us.map(|peers| peers.to_string()),
+        Header::SmallStatus => torrent.status.map(|status| format!("{status:?}")),
+    };
+    text.unwrap_or_else(|| "?".to_string())
+}
+
+fn relative_time_text(timestamp: i64) -> String {
+    seconds_to_relative_format(chrono::Utc::now().timestamp() - timestamp)
+}
@@ -1,26 +1,100 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-use rm_config::CONFIG;
-use tokio::sync::oneshot;
+use anyhow::Result;
+use regex::Regex;
+use rm_config::{main_config::RssFeed, state::State, CONFIG};
+use tokio::sync::{oneshot, watch};
 use transmission_rpc::types::TorrentGetField;
 
-use rm_shared::action::UpdateAction;
+use rm_shared::{
+    action::{ErrorMessage, UpdateAction},
+    status_task::StatusTask,
+};
 
 use crate::tui::app;
 
 use super::TorrentAction;
 
-pub async fn stats(ctx: app::Ctx) {
+const MAX_BACKOFF_SECS: u64 = 60;
+// How many times in a row fetching has to fail before we bother the user with a popup
+const FAILURES_BEFORE_POPUP: u32 = 5;
+
+fn backoff_delay(failures: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(failures).min(MAX_BACKOFF_SECS))
+}
+
+/// Shared by the fetchers, so that the connection is reported as lost when the
+/// first of them starts failing and as restored only once all of them got through.
+#[derive(Clone, Default)]
+pub struct Connection {
+    failing_fetchers: Arc<AtomicUsize>,
+}
+
+// Failures in a row of a single fetcher
+struct Failures {
+    ctx: app::Ctx,
+    connection: Connection,
+    count: u32,
+}
+
+impl Failures {
+    const fn new(ctx: app::Ctx, connection: Connection) -> Self {
+        Self {
+            ctx,
+            connection,
+            count: 0,
+        }
+    }
+
+    fn succeeded(&mut self) {
+        if self.count > 0 {
+            self.count = 0;
+            let failing = &self.connection.failing_fetchers;
+            if failing.fetch_sub(1, Ordering::Relaxed) == 1 {
+                self.ctx
+                    .send_update_action(UpdateAction::ConnectionRestored);
+            }
+        }
+    }
+
+    // Waits before the next try, longer the more times it failed
+    async fn failed(&mut self, err_message: Box<ErrorMessage>) {
+        self.count += 1;
+        if self.count == 1 {
+            let failing = &self.connection.failing_fetchers;
+            if failing.fetch_add(1, Ordering::Relaxed) == 0 {
+                self.ctx.send_update_action(UpdateAction::ConnectionLost);
+            }
+        }
+        if self.count == FAILURES_BEFORE_POPUP {
+            self.ctx
+                .send_update_action(UpdateAction::Error(err_message));
+        }
+        tokio::time::sleep(backoff_delay(self.count)).await;
+    }
+}
+
+pub async fn stats(ctx: app::Ctx, connection: Connection) {
+    let mut failures = Failures::new(ctx.clone(), connection);
     loop {
         let (stats_tx, stats_rx) = oneshot::channel();
         ctx.send_torrent_action(TorrentAction::GetSessionStats(stats_tx));
 
         match stats_rx.await.unwrap() {
             Ok(stats) => {
+                failures.succeeded();
                 ctx.send_update_action(UpdateAction::SessionStats(stats));
             }
             Err(err_message) => {
-                ctx.send_update_action(UpdateAction::Error(err_message));
+                failures.failed(err_message).await;
+                continue;
             }
         };
 
@@ -28,18 +102,17 @@ pub async fn stats(ctx: app::Ctx) {
     }
 }
 
-pub async fn free_space(ctx: app::Ctx) {
+pub async fn free_space(ctx: app::Ctx, connection: Connection) {
+    let mut failures = Failures::new(ctx.clone(), connection);
     let download_dir = loop {
         let (sess_tx, sess_rx) = oneshot::channel();
         ctx.send_torrent_action(TorrentAction::GetSessionGet(sess_tx));
         match sess_rx.await.unwrap() {
             Ok(sess) => {
+                failures.succeeded();
                 break sess.download_dir.leak();
             }
-            Err(err_message) => {
-                ctx.send_update_action(UpdateAction::Error(err_message));
-                tokio::time::sleep(Duration::from_secs(10)).await;
-            }
+            Err(err_message) => failures.failed(err_message).await,
         };
     };
 
@@ -52,10 +125,12 @@ pub async fn free_space(ctx: app::Ctx) {
 
         match space_rx.await.unwrap() {
             Ok(free_space) => {
+                failures.succeeded();
                 ctx.send_update_action(UpdateAction::FreeSpace(Arc::new(free_space)));
             }
             Err(err_message) => {
-                ctx.send_update_action(UpdateAction::Error(err_message));
+                failures.failed(err_message).await;
+                continue;
             }
         }
 
@@ -63,40 +138,144 @@ pub async fn free_space(ctx: app::Ctx) {
     }
 }
 
-pub async fn torrents(ctx: app::Ctx) {
+// `fields_rx` has the fields needed for whatever is currently shown
+pub async fn torrents(
+    ctx: app::Ctx,
+    connection: Connection,
+    mut fields_rx: watch::Receiver<Vec<TorrentGetField>>,
+) {
+    let mut failures = Failures::new(ctx.clone(), connection);
     loop {
-        let fields = vec![
-            TorrentGetField::Id,
-            TorrentGetField::Name,
-            TorrentGetField::IsFinished,
-            TorrentGetField::IsStalled,
-            TorrentGetField::PercentDone,
-            TorrentGetField::UploadRatio,
-            TorrentGetField::SizeWhenDone,
-            TorrentGetField::Eta,
-            TorrentGetField::RateUpload,
-            TorrentGetField::RateDownload,
-            TorrentGetField::Status,
-            TorrentGetField::DownloadDir,
-            TorrentGetField::UploadedEver,
-            TorrentGetField::ActivityDate,
-            TorrentGetField::AddedDate,
-            TorrentGetField::PeersConnected,
-            TorrentGetField::Error,
-            TorrentGetField::ErrorString,
-        ];
+        let fields = fields_rx.borrow_and_update().clone();
         let (torrents_tx, torrents_rx) = oneshot::channel();
-        ctx.send_torrent_action(TorrentAction::GetTorrents(fields, torrents_tx));
+        let requested_at = Instant::now();
+        ctx.send_torrent_action(TorrentAction::GetTorrents(fields.clone(), torrents_tx));
 
         match torrents_rx.await.unwrap() {
-            Ok(torrents) => {
-                ctx.send_update_action(UpdateAction::UpdateTorrents(torrents));
+            Ok((torrents, limits)) => {
+                failures.succeeded();
+                ctx.send_update_action(UpdateAction::UpdateTorrents(torrents, fields));
+                ctx.send_update_action(UpdateAction::UploadLimits(limits, requested_at));
             }
             Err(err_message) => {
-                ctx.send_update_action(UpdateAction::Error(err_message));
+                failures.failed(err_message).await;
+                continue;
             }
         };
 
-        tokio::time::sleep(Duration::from_secs(CONFIG.connection.torrents_refresh)).await;
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(CONFIG.connection.torrents_refresh)) => (),
+            // Something that needs more fields got shown, or what's shown is off,
+            // no point in waiting
+            Ok(()) = fields_rx.changed() => (),
+        }
+    }
+}
+
+// Item's ID and the link to its torrent
+fn rss_item_link(item: &rss::Item) -> Option<(String, String)> {
+    let link = item
+        .enclosure()
+        .map(|enclosure| enclosure.url())
+        .or(item.link())?;
+    let id = item.guid().map_or(link, |guid| guid.value());
+    Some((id.to_string(), link.to_string()))
+}
+
+async fn fetch_rss_channel(feed: &RssFeed) -> Result<rss::Channel> {
+    let content = reqwest::get(feed.url.clone()).await?.bytes().await?;
+    Ok(rss::Channel::read_from(&content[..])?)
+}
+
+pub async fn rss_feed(ctx: app::Ctx, feed: &'static RssFeed) {
+    let filter = match feed.filter.as_deref().map(Regex::new).transpose() {
+        Ok(filter) => filter,
+        Err(err) => {
+            let msg = format!("Invalid filter for RSS feed {}", feed.url);
+            let err_message = ErrorMessage::new("RSS feed error", msg, Box::new(err));
+            ctx.send_update_action(UpdateAction::Error(Box::new(err_message)));
+            return;
+        }
+    };
+
+    let state = State::load();
+    let mut seeded = state.rss_seeded_feeds.contains(&feed.url.to_string());
+    let mut seen: HashSet<String> = state.rss_seen.into_iter().collect();
+    let mut failing = false;
+    loop {
+        match fetch_rss_channel(feed).await {
+            Ok(channel) if !seeded => {
+                // Only what shows up from now on gets added, not the whole backlog
+                failing = false;
+                seeded = true;
+                let ids: Vec<String> = channel
+                    .items()
+                    .iter()
+                    .filter_map(|item| rss_item_link(item).map(|(id, _)| id))
+                    .collect();
+                seen.extend(ids.iter().cloned());
+                State::remember_rss_feed_seeded(feed.url.to_string(), ids);
+            }
+            Ok(channel) => {
+                failing = false;
+                let new_items: Vec<(String, String, String)> = channel
+                    .items()
+                    .iter()
+                    .filter(|item| {
+                        let title = item.title().unwrap_or_default();
+                        filter.as_ref().is_none_or(|filter| filter.is_match(title))
+                    })
+                    .filter_map(|item| {
+                        let (id, link) = rss_item_link(item)?;
+                        let title = item.title().unwrap_or(&link).to_string();
+                        (!seen.contains(&id)).then_some((id, link, title))
+                    })
+                    .collect();
+
+                if !new_items.is_empty() {
+                    let what = match new_items.as_slice() {
+                        [(_, _, title)] => title.clone(),
+                        items => format!("{} torrents from RSS", items.len()),
+                    };
+                    ctx.send_update_action(UpdateAction::TaskSet(StatusTask::new_add(what)));
+
+                    let mut added = vec![];
+                    let mut all_added = true;
+                    for (id, link, _) in new_items {
+                        let (added_tx, added_rx) = oneshot::channel();
+                        ctx.send_torrent_action(TorrentAction::AddQueued(
+                            link,
+                            feed.download_dir.clone(),
+                            Some(added_tx),
+                        ));
+                        // Ones that failed get another try on the next refresh
+                        if added_rx.await.unwrap_or(false) {
+                            seen.insert(id.clone());
+                            added.push(id);
+                        } else {
+                            all_added = false;
+                        }
+                    }
+
+                    if all_added {
+                        ctx.send_update_action(UpdateAction::TaskSuccess);
+                    } else {
+                        ctx.send_update_action(UpdateAction::TaskFailure);
+                    }
+                    State::remember_rss_items(added);
+                }
+            }
+            Err(err) => {
+                // Only report the first failure, the feed may come back by itself
+                if !failing {
+                    failing = true;
+                    let msg = format!("Failed to fetch RSS feed {}", feed.url);
+                    let err_message = ErrorMessage::new("RSS feed error", msg, err.into());
+                    ctx.send_update_action(UpdateAction::Error(Box::new(err_message)));
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(feed.refresh)).await;
     }
 }
@@ -1,5 +1,8 @@
 mod action;
+pub mod columns;
 pub mod fetchers;
+mod raw;
 pub mod utils;
 
 pub use action::{action_handler, TorrentAction};
+pub use raw::RawClient;
@@ -0,0 +1,112 @@
+use anyhow::{bail, Result};
+use reqwest::{header::HeaderValue, StatusCode};
+use rm_config::CONFIG;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use transmission_rpc::types::Id;
+
+const SESSION_ID_HEADER: &str = "X-Transmission-Session-Id";
+
+// transmission-rpc only deserializes a handful of session-get and torrent-get fields,
+// so anything else we ask the daemon for directly. Same for torrent-set arguments
+// it gets wrong, like trackerRemove taking tracker IDs rather than announce URLs.
+pub struct RawClient {
+    http: reqwest::Client,
+    session_id: Option<HeaderValue>,
+}
+
+#[derive(Serialize)]
+struct RpcRequest<'a, A> {
+    method: &'a str,
+    arguments: A,
+}
+
+#[derive(Serialize)]
+struct FieldsArgs<'a> {
+    fields: &'a [&'a str],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ids: Option<&'a [Id]>,
+}
+
+#[derive(Serialize)]
+struct SetArgs<'a, A> {
+    ids: &'a [Id],
+    #[serde(flatten)]
+    args: A,
+}
+
+#[derive(Deserialize)]
+struct TorrentsArgs<T> {
+    torrents: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    arguments: T,
+    result: String,
+}
+
+impl RawClient {
+    pub const fn new(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            session_id: None,
+        }
+    }
+
+    pub async fn session_get<T: DeserializeOwned>(&mut self, fields: &[&str]) -> Result<T> {
+        self.request("session-get", FieldsArgs { fields, ids: None })
+            .await
+    }
+
+    pub async fn torrent_get<T: DeserializeOwned>(
+        &mut self,
+        ids: Option<&[Id]>,
+        fields: &[&str],
+    ) -> Result<Vec<T>> {
+        let arguments = FieldsArgs { fields, ids };
+        let response: TorrentsArgs<T> = self.request("torrent-get", arguments).await?;
+        Ok(response.torrents)
+    }
+
+    pub async fn torrent_set(&mut self, ids: &[Id], args: impl Serialize) -> Result<()> {
+        let _: IgnoredAny = self.request("torrent-set", SetArgs { ids, args }).await?;
+        Ok(())
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        arguments: impl Serialize,
+    ) -> Result<T> {
+        let request = RpcRequest { method, arguments };
+
+        // The first request (and any after the daemon restarts) is rejected with 409
+        // and the session id we're supposed to use.
+        for _ in 0..2 {
+            let mut builder = self.http.post(CONFIG.connection.url.clone()).json(&request);
+            if let Some(user) = &CONFIG.connection.username {
+                builder = builder.basic_auth(user, CONFIG.connection.password.as_ref());
+            }
+            if let Some(session_id) = &self.session_id {
+                builder = builder.header(SESSION_ID_HEADER, session_id);
+            }
+
+            let response = builder.send().await?;
+            if response.status() == StatusCode::CONFLICT {
+                self.session_id = response.headers().get(SESSION_ID_HEADER).cloned();
+                continue;
+            }
+
+            let response: RpcResponse<T> = response.error_for_status()?.json().await?;
+            if response.result != "success" {
+                bail!(response.result);
+            }
+            return Ok(response.arguments);
+        }
+
+        bail!("Transmission kept rejecting the session id")
+    }
+}
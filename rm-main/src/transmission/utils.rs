@@ -1,7 +1,37 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use reqwest::{Certificate, Client};
 use rm_config::CONFIG;
 use transmission_rpc::{types::BasicAuth, TransClient};
 
-pub fn new_client() -> TransClient {
+pub const INSECURE_CONNECTION_WARNING: &str =
+    "Certificate verification is disabled (accept_invalid_certs), so the connection to Transmission isn't secure.";
+
+// HTTP client that trusts the configured CA certificate, shared by all RPC clients.
+pub fn http_client() -> Result<Client> {
+    let mut builder = Client::builder();
+
+    if let Some(path) = &CONFIG.connection.ca_cert {
+        let cert = fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| {
+                Certificate::from_pem(&bytes)
+                    .or_else(|_| Certificate::from_der(&bytes))
+                    .map_err(anyhow::Error::from)
+            })
+            .with_context(|| format!("Failed to load the CA certificate from {path:?}"))?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    if CONFIG.connection.accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder.build()?)
+}
+
+pub fn new_client() -> Result<TransClient> {
     let user = CONFIG
         .connection
         .username
@@ -17,5 +47,7 @@ pub fn new_client() -> TransClient {
 
     let auth = BasicAuth { user, password };
 
-    TransClient::with_auth(CONFIG.connection.url.clone(), auth)
+    let mut client = TransClient::new_with_client(CONFIG.connection.url.clone(), http_client()?);
+    client.set_auth(auth);
+    Ok(client)
 }
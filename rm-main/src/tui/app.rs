@@ -1,26 +1,40 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use crate::{
-    transmission::{self, TorrentAction},
+    transmission::{self, RawClient, TorrentAction},
     tui::components::Component,
 };
 
 use rm_config::CONFIG;
-use rm_shared::action::{Action, UpdateAction};
+use rm_shared::action::{Action, ErrorMessage, UpdateAction};
 
 use anyhow::{Error, Result};
 use crossterm::event::{Event, KeyCode, KeyModifiers};
-use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::MissedTickBehavior,
+};
 use transmission_rpc::{types::SessionGet, TransClient};
 
 use super::{components::CurrentTab, main_window::MainWindow, terminal::Tui};
 
+// Renders requested in between frames (e.g. while a key is held) get drawn together
+const FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 60);
+
 #[derive(Clone)]
 pub struct Ctx {
     pub session_info: Arc<SessionGet>,
     action_tx: UnboundedSender<Action>,
     update_tx: UnboundedSender<UpdateAction>,
     trans_tx: UnboundedSender<TorrentAction>,
+    // Torrent actions sent but not yet handled
+    rpc_calls: Arc<AtomicUsize>,
 }
 
 impl Ctx {
@@ -29,6 +43,7 @@ impl Ctx {
         action_tx: UnboundedSender<Action>,
         update_tx: UnboundedSender<UpdateAction>,
         trans_tx: UnboundedSender<TorrentAction>,
+        rpc_calls: Arc<AtomicUsize>,
     ) -> Result<Self> {
         let response = client.session_get().await;
         match response {
@@ -38,6 +53,7 @@ impl Ctx {
                     action_tx,
                     trans_tx,
                     update_tx,
+                    rpc_calls,
                     session_info,
                 })
             }
@@ -56,9 +72,14 @@ impl Ctx {
     }
 
     pub(crate) fn send_torrent_action(&self, action: TorrentAction) {
+        self.rpc_calls.fetch_add(1, Ordering::Relaxed);
         self.trans_tx.send(action).unwrap();
     }
 
+    pub(crate) fn rpc_calls_in_flight(&self) -> usize {
+        self.rpc_calls.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn send_update_action(&self, action: UpdateAction) {
         self.update_tx.send(action).unwrap();
     }
@@ -71,6 +92,7 @@ pub struct App {
     update_rx: UnboundedReceiver<UpdateAction>,
     main_window: MainWindow,
     mode: Mode,
+    render_needed: bool,
 }
 
 impl App {
@@ -78,12 +100,32 @@ impl App {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
         let (update_tx, update_rx) = mpsc::unbounded_channel();
 
-        let mut client = transmission::utils::new_client();
+        let mut client = transmission::utils::new_client()?;
+        let raw_client = RawClient::new(transmission::utils::http_client()?);
 
         let (trans_tx, trans_rx) = mpsc::unbounded_channel();
-        let ctx = Ctx::new(&mut client, action_tx.clone(), update_tx.clone(), trans_tx).await?;
-
-        tokio::spawn(transmission::action_handler(client, trans_rx, update_tx));
+        let rpc_calls = Arc::new(AtomicUsize::new(0));
+        let ctx = Ctx::new(
+            &mut client,
+            action_tx.clone(),
+            update_tx.clone(),
+            trans_tx,
+            Arc::clone(&rpc_calls),
+        )
+        .await?;
+
+        tokio::spawn(transmission::action_handler(
+            client, raw_client, trans_rx, update_tx, rpc_calls,
+        ));
+
+        if CONFIG.connection.accept_invalid_certs {
+            let err_message = ErrorMessage::new(
+                "Insecure connection",
+                "You can turn verification back on in the config.",
+                Error::msg(transmission::utils::INSECURE_CONNECTION_WARNING).into(),
+            );
+            ctx.send_update_action(UpdateAction::Error(Box::new(err_message)));
+        }
 
         Ok(Self {
             should_quit: false,
@@ -92,6 +134,7 @@ impl App {
             update_rx,
             ctx,
             mode: Mode::Normal,
+            render_needed: false,
         })
     }
 
@@ -110,17 +153,25 @@ impl App {
 
     async fn main_loop(&mut self, tui: &mut Tui) -> Result<()> {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(250));
+        let mut frame_interval = tokio::time::interval(FRAME_INTERVAL);
+        frame_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
         loop {
             let tui_event = tui.next();
             let action = self.action_rx.recv();
             let update_action = self.update_rx.recv();
             let tick_action = interval.tick();
+            let frame_tick = frame_interval.tick();
 
             let current_tab = self.main_window.tabs.current_tab;
 
             tokio::select! {
                 _ = tick_action => self.tick(),
 
+                _ = frame_tick, if self.render_needed => {
+                    self.render_needed = false;
+                    self.render(tui)?;
+                },
+
                 event = tui_event => {
                     event_to_action(&self.ctx, self.mode, current_tab, event.unwrap());
                 },
@@ -130,7 +181,7 @@ impl App {
                 action = action => {
                     if let Some(action) = action {
                         if action.is_render() {
-                            self.render(tui)?;
+                            self.render_needed = true;
                         } else {
                             self.handle_user_action(action).await
                         }
@@ -202,6 +253,7 @@ pub fn event_to_action(ctx: &Ctx, mode: Mode, current_tab: CurrentTab, event: Ev
 
     match event {
         Event::Key(key) if mode == Mode::Input => ctx.send_action(Action::Input(key)),
+        Event::Paste(text) if mode == Mode::Input => ctx.send_action(Action::Paste(text)),
         Event::Key(key) => {
             let keymaps = match current_tab {
                 CurrentTab::Torrents => [
@@ -233,6 +285,7 @@ pub fn event_to_action(ctx: &Ctx, mode: Mode, current_tab: CurrentTab, event: Ev
                 }
             }
         }
+        Event::Mouse(mouse) if mode == Mode::Normal => ctx.send_action(Action::Mouse(mouse)),
         Event::Resize(_, _) => ctx.send_action(Action::Render),
         _ => (),
     }
@@ -0,0 +1,50 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use anyhow::{bail, Result};
+
+// Clipboard tools to try, in order, as (program, arguments)
+fn clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        return vec![("pbcopy", &[])];
+    }
+    if cfg!(target_os = "windows") {
+        return vec![("clip", &[])];
+    }
+
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = vec![];
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard"]));
+    commands.push(("xsel", &["--clipboard", "--input"]));
+    commands
+}
+
+// Hands the text over to the first clipboard tool that's installed.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    for (program, args) in clipboard_commands() {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    bail!("No clipboard found. Install wl-clipboard, xclip or xsel.")
+}
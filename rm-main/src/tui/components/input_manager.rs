@@ -4,7 +4,7 @@ use ratatui::{
     widgets::{Clear, Paragraph},
 };
 use rm_config::CONFIG;
-use tui_input::{backend::crossterm::to_input_request, Input, InputResponse};
+use tui_input::{backend::crossterm::to_input_request, Input, InputRequest, InputResponse};
 
 use crate::tui::components::Component;
 
@@ -32,6 +32,10 @@ impl InputManager {
         self.input.to_string()
     }
 
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.input = Input::default().with_value(text.into());
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> InputResponse {
         let event = Event::Key(key);
 
@@ -41,6 +45,16 @@ impl InputManager {
             None
         }
     }
+
+    // Inserts pasted text at the cursor, leaving out newlines and such.
+    // Returns whether anything got inserted.
+    pub fn paste(&mut self, text: &str) -> bool {
+        let mut changed = false;
+        for c in text.chars().filter(|c| !c.is_control()) {
+            changed |= self.input.handle(InputRequest::InsertChar(c)).is_some();
+        }
+        changed
+    }
 }
 
 impl Component for InputManager {
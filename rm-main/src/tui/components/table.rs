@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, ops::Range};
 
 use ratatui::widgets::TableState;
 
@@ -27,6 +27,36 @@ impl<T: Clone> GenericTable<T> {
         *self.overwritten_len.borrow_mut() = Some(len);
     }
 
+    // Moves the selection onto the last item if it ended up past it,
+    // e.g. after the items got filtered.
+    pub fn clamp_selection(&self) {
+        let len = self.get_len();
+        let mut state = self.state.borrow_mut();
+        if let Some(selected) = state.selected() {
+            if len > 0 && selected >= len {
+                state.select(Some(len - 1));
+            }
+        }
+    }
+
+    // Scrolls just enough for the selected item to fit in a viewport this high
+    // and returns the range of items that are visible in it.
+    pub fn visible_range(&self, viewport_height: usize) -> Range<usize> {
+        let len = self.get_len();
+        let mut state = self.state.borrow_mut();
+        let selected = state.selected().unwrap_or_default();
+
+        let mut offset = state.offset().min(len.saturating_sub(viewport_height));
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + viewport_height {
+            offset = selected + 1 - viewport_height;
+        }
+        *state.offset_mut() = offset;
+
+        offset..(offset + viewport_height).min(len)
+    }
+
     pub fn set_items(&mut self, items: Vec<T>) {
         self.items = items;
     }
@@ -82,7 +112,7 @@ impl<T: Clone> GenericTable<T> {
         let mut state = self.state.borrow_mut();
         let new_selection = state.selected().unwrap_or_default() + amount;
 
-        if new_selection > self.get_len() {
+        if new_selection >= self.get_len() {
             state.select(Some(self.get_len() - 1));
         } else {
             state.select(Some(new_selection));
@@ -108,11 +138,11 @@ impl<T: Clone> GenericTable<T> {
     }
 
     pub fn scroll_to_end(&mut self) {
-        if self.items.is_empty() {
+        if self.get_len() == 0 {
             return;
         }
 
         let mut state = self.state.borrow_mut();
-        state.select(Some(self.items.len() - 1));
+        state.select(Some(self.get_len() - 1));
     }
 }
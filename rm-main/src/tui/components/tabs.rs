@@ -1,4 +1,4 @@
-use rm_config::CONFIG;
+use rm_config::{state::State, CONFIG};
 use rm_shared::action::Action;
 
 use crate::tui::app;
@@ -12,6 +12,15 @@ pub enum CurrentTab {
     Search,
 }
 
+impl CurrentTab {
+    const fn from_index(index: u8) -> Self {
+        match index {
+            1 => Self::Search,
+            _ => Self::Torrents,
+        }
+    }
+}
+
 pub struct TabComponent {
     tabs_list: [&'static str; 2],
     pub current_tab: CurrentTab,
@@ -31,13 +40,14 @@ impl TabComponent {
         Self {
             ctx,
             tabs_list,
-            current_tab: CurrentTab::Torrents,
+            current_tab: CurrentTab::from_index(State::load().active_tab),
         }
     }
 
     fn switch_to(&mut self, new_tab: CurrentTab) {
         if self.current_tab != new_tab {
             self.current_tab = new_tab;
+            State::remember_active_tab(new_tab as u8);
             self.ctx.send_action(Action::Render);
         }
     }
@@ -0,0 +1,70 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use rm_config::CONFIG;
+use rm_shared::action::Action;
+
+use crate::tui::{
+    app,
+    components::{Component, ComponentAction},
+    main_window::centered_rect,
+};
+
+pub struct ConfirmQuitPopup {
+    ctx: app::Ctx,
+}
+
+impl ConfirmQuitPopup {
+    pub const fn new(ctx: app::Ctx) -> Self {
+        Self { ctx }
+    }
+}
+
+impl Component for ConfirmQuitPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Confirm | Action::Quit => {
+                self.ctx.send_action(Action::HardQuit);
+                ComponentAction::Nothing
+            }
+            _ if action.is_soft_quit() => ComponentAction::Quit,
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, _rect: Rect) {
+        let centered_rect = centered_rect(f.size(), 50, 30);
+        let popup_rect = centered_rect.inner(Margin::new(1, 1));
+        let text_rect = popup_rect.inner(Margin::new(3, 2));
+        let button_rect = Layout::vertical([Constraint::Percentage(100), Constraint::Length(1)])
+            .split(text_rect)[1];
+
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+        let buttons = Line::from(vec![
+            Span::styled("[ QUIT ]", accent_style.bold()),
+            Span::raw(" "),
+            Span::raw("[ CANCEL ]"),
+        ])
+        .right_aligned();
+
+        let block = Block::bordered()
+            .border_set(symbols::border::ROUNDED)
+            .title_style(accent_style)
+            .title(" Quit ");
+
+        let lines = vec![
+            Line::from("Some torrents are still downloading."),
+            Line::default(),
+            Line::from("Quitting rustmission won't stop them, downloads continue on the server."),
+        ];
+
+        let message = Paragraph::new(lines).wrap(Wrap { trim: false });
+
+        f.render_widget(Clear, centered_rect);
+        f.render_widget(block, popup_rect);
+        f.render_widget(message, text_rect);
+        f.render_widget(buttons, button_rect);
+    }
+}
@@ -1,8 +1,10 @@
+mod confirm_quit;
 mod error;
 mod help;
 
 use ratatui::prelude::*;
 
+pub use confirm_quit::ConfirmQuitPopup;
 pub use error::ErrorPopup;
 pub use help::HelpPopup;
 
@@ -15,6 +17,7 @@ use super::components::{Component, ComponentAction};
 pub(super) struct GlobalPopupManager {
     pub error_popup: Option<ErrorPopup>,
     pub help_popup: Option<HelpPopup>,
+    pub confirm_quit_popup: Option<ConfirmQuitPopup>,
     ctx: app::Ctx,
 }
 
@@ -23,12 +26,13 @@ impl GlobalPopupManager {
         Self {
             error_popup: None,
             help_popup: None,
+            confirm_quit_popup: None,
             ctx,
         }
     }
 
     pub const fn needs_action(&self) -> bool {
-        self.error_popup.is_some() || self.help_popup.is_some()
+        self.error_popup.is_some() || self.help_popup.is_some() || self.confirm_quit_popup.is_some()
     }
 
     fn toggle_help(&mut self) {
@@ -50,6 +54,11 @@ impl GlobalPopupManager {
                 self.help_popup = None;
                 self.ctx.send_action(Action::Render);
             }
+        } else if let Some(popup) = &mut self.confirm_quit_popup {
+            if popup.handle_actions(action).is_quit() {
+                self.confirm_quit_popup = None;
+                self.ctx.send_action(Action::Render);
+            }
         }
     }
 }
@@ -72,6 +81,8 @@ impl Component for GlobalPopupManager {
             popup.render(f, rect)
         } else if let Some(popup) = &mut self.help_popup {
             popup.render(f, rect);
+        } else if let Some(popup) = &mut self.confirm_quit_popup {
+            popup.render(f, rect);
         }
     }
 }
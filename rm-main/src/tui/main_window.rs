@@ -1,21 +1,37 @@
-use ratatui::prelude::*;
+use ratatui::{
+    layout::Flex,
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
 
-use rm_shared::action::{Action, UpdateAction};
+use rm_config::CONFIG;
+use rm_shared::{
+    action::{Action, UpdateAction},
+    header::Header,
+};
 
 use crate::tui::components::CurrentTab;
 
 use super::{
     app,
     components::{Component, ComponentAction, TabComponent},
-    global_popups::{ErrorPopup, GlobalPopupManager},
+    global_popups::{ConfirmQuitPopup, ErrorPopup, GlobalPopupManager},
     tabs::{search::SearchTab, torrents::TorrentsTab},
 };
 
+// Narrowest a torrent name can get while staying readable
+const MIN_NAME_WIDTH: u16 = 20;
+// Columns that should still fit next to the name
+const MIN_WIDTH_HEADERS: [Header; 2] = [Header::SizeWhenDone, Header::Progress];
+// Tabs bar, the torrents table's 10 rows and the stats bar
+const MIN_HEIGHT: u16 = 1 + 10 + 1;
+
 pub struct MainWindow {
     pub tabs: TabComponent,
     torrents_tab: TorrentsTab,
     search_tab: SearchTab,
     global_popup_manager: GlobalPopupManager,
+    ctx: app::Ctx,
 }
 
 impl MainWindow {
@@ -24,7 +40,18 @@ impl MainWindow {
             tabs: TabComponent::new(ctx.clone()),
             torrents_tab: TorrentsTab::new(ctx.clone()),
             search_tab: SearchTab::new(ctx.clone()),
-            global_popup_manager: GlobalPopupManager::new(ctx),
+            global_popup_manager: GlobalPopupManager::new(ctx.clone()),
+            ctx,
+        }
+    }
+
+    fn quit(&mut self) {
+        if CONFIG.general.confirm_quit && self.torrents_tab.has_active_downloads() {
+            self.global_popup_manager.confirm_quit_popup =
+                Some(ConfirmQuitPopup::new(self.ctx.clone()));
+            self.ctx.send_action(Action::Render);
+        } else {
+            self.ctx.send_action(Action::HardQuit);
         }
     }
 }
@@ -44,10 +71,14 @@ impl Component for MainWindow {
                 self.tabs.handle_actions(action);
             }
             _ if self.tabs.current_tab == CurrentTab::Torrents => {
-                self.torrents_tab.handle_actions(action);
+                if self.torrents_tab.handle_actions(action).is_quit() {
+                    self.quit();
+                }
             }
             _ if self.tabs.current_tab == CurrentTab::Search => {
-                self.search_tab.handle_actions(action);
+                if self.search_tab.handle_actions(action).is_quit() {
+                    self.quit();
+                }
             }
             _ => unreachable!(),
         };
@@ -77,6 +108,11 @@ impl Component for MainWindow {
     }
 
     fn render(&mut self, f: &mut Frame, rect: Rect) {
+        if rect.width < min_width() || rect.height < MIN_HEIGHT {
+            render_too_small(f, rect);
+            return;
+        }
+
         let [top_bar, main_window] =
             Layout::vertical([Constraint::Length(1), Constraint::Percentage(100)]).areas(rect);
 
@@ -91,6 +127,36 @@ impl Component for MainWindow {
     }
 }
 
+// The name plus the other columns at the widths the torrents table gives them,
+// with a cell of spacing before each
+fn min_width() -> u16 {
+    MIN_WIDTH_HEADERS
+        .iter()
+        .map(|header| header.fixed_width().unwrap_or_default() + 1)
+        .fold(MIN_NAME_WIDTH, u16::saturating_add)
+}
+
+fn render_too_small(f: &mut Frame, rect: Rect) {
+    let message = format!(
+        "Terminal too small ({}x{}), need {}x{MIN_HEIGHT}",
+        rect.width,
+        rect.height,
+        min_width()
+    );
+    // Roughly how many lines it takes once wrapped
+    let height = u16::try_from(message.chars().count())
+        .unwrap_or(u16::MAX)
+        .div_ceil(rect.width.max(1));
+    let paragraph = Paragraph::new(message)
+        .fg(CONFIG.theme.error)
+        .centered()
+        .wrap(Wrap { trim: true });
+    let [message_rect] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(rect);
+    f.render_widget(paragraph, message_rect);
+}
+
 pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let popup_layout = Layout::vertical([
         Constraint::Percentage((100 - percent_y) / 2),
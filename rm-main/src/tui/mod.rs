@@ -1,4 +1,5 @@
 pub mod app;
+mod clipboard;
 mod components;
 mod global_popups;
 pub mod main_window;
@@ -70,6 +70,8 @@ impl SearchState {
     }
 
     pub fn update_counts(&mut self, providers: &Vec<ConfiguredProvider>) {
+        self.providers_finished = 0;
+        self.providers_errored = 0;
         for provider in providers {
             if provider.enabled {
                 if matches!(provider.provider_state, ProviderState::Found(_)) {
@@ -82,9 +84,16 @@ impl SearchState {
     }
 
     pub fn searching(&mut self) {
+        self.providers_finished = 0;
+        self.providers_errored = 0;
         self.stage = SearchStage::Searching(ThrobberState::default());
     }
 
+    pub fn reset(&mut self) {
+        self.stage = SearchStage::Nothing;
+        self.ctx.send_action(Action::Render);
+    }
+
     pub fn not_found(&mut self) {
         self.stage = SearchStage::NoResults;
     }
@@ -121,10 +130,9 @@ impl Component for SearchState {
         match &mut self.stage {
             SearchStage::Nothing => (),
             SearchStage::Searching(ref mut state) => {
-                let label = format!(
-                    "Searching... {:.0}%",
-                    self.providers_finished / self.providers_count
-                );
+                let providers_done = u32::from(self.providers_finished + self.providers_errored);
+                let percent = providers_done * 100 / u32::from(self.providers_count.max(1));
+                let label = format!("Searching... {percent}%");
                 let default_throbber = throbber_widgets_tui::Throbber::default()
                     .label(label)
                     .style(ratatui::style::Style::default().fg(ratatui::style::Color::Yellow));
@@ -1,7 +1,7 @@
 mod bottom_bar;
 mod popups;
 
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, sync::Arc, time::Duration};
 
 use bottom_bar::BottomBar;
 use crossterm::event::{Event, KeyCode, KeyEvent};
@@ -15,8 +15,8 @@ use ratatui::{
 };
 use reqwest::Client;
 use rm_config::CONFIG;
-use tokio::sync::mpsc::{self, UnboundedSender};
-use tui_input::{backend::crossterm::to_input_request, Input};
+use tokio::task::JoinHandle;
+use tui_input::{backend::crossterm::to_input_request, Input, InputRequest};
 
 use crate::{
     transmission::TorrentAction,
@@ -25,10 +25,10 @@ use crate::{
         components::{Component, ComponentAction, GenericTable},
     },
 };
-use rm_shared::{
-    action::{Action, UpdateAction},
-    utils::bytes_to_human_format,
-};
+use rm_shared::action::{Action, UpdateAction};
+
+// How long typing has to pause before the search starts on its own
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(400);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum SearchTabFocus {
@@ -39,7 +39,8 @@ enum SearchTabFocus {
 pub(crate) struct SearchTab {
     focus: SearchTabFocus,
     input: Input,
-    search_query_rx: UnboundedSender<String>,
+    client: Client,
+    search_task_handle: Option<JoinHandle<()>>,
     table: GenericTable<Magnet>,
     popup_manager: PopupManager,
     configured_providers: Vec<ConfiguredProvider>,
@@ -50,7 +51,6 @@ pub(crate) struct SearchTab {
 
 impl SearchTab {
     pub(crate) fn new(ctx: app::Ctx) -> Self {
-        let (search_query_tx, mut search_query_rx) = mpsc::unbounded_channel::<String>();
         let table = GenericTable::new(vec![]);
 
         let mut configured_providers = vec![];
@@ -71,39 +71,13 @@ impl SearchTab {
 
         let bottom_bar = BottomBar::new(ctx.clone(), &configured_providers);
 
-        tokio::task::spawn({
-            let ctx = ctx.clone();
-            let configured_providers = configured_providers.clone();
-            async move {
-                let client = Client::new();
-                while let Some(phrase) = search_query_rx.recv().await {
-                    ctx.send_update_action(UpdateAction::SearchStarted);
-                    let mut futures = FuturesUnordered::new();
-                    for configured_provider in &configured_providers {
-                        if configured_provider.enabled {
-                            futures.push(configured_provider.provider.search(&client, &phrase));
-                        }
-                    }
-
-                    while let Some(result) = futures.next().await {
-                        match result {
-                            Ok(response) => {
-                                ctx.send_update_action(UpdateAction::ProviderResult(response))
-                            }
-                            Err(e) => ctx.send_update_action(UpdateAction::ProviderError(e)),
-                        }
-                    }
-                    ctx.send_update_action(UpdateAction::SearchFinished);
-                }
-            }
-        });
-
         Self {
             focus: SearchTabFocus::List,
             input: Input::default(),
+            client: Client::new(),
+            search_task_handle: None,
             table,
             bottom_bar,
-            search_query_rx: search_query_tx,
             currently_displaying_no: 0,
             popup_manager: PopupManager::new(ctx.clone()),
             ctx,
@@ -111,8 +85,8 @@ impl SearchTab {
         }
     }
 
-    fn magnet_to_row(magnet: &Magnet) -> Row {
-        let size = bytes_to_human_format(magnet.bytes as i64);
+    fn magnet_to_row(magnet: &Magnet) -> Row<'_> {
+        let size = CONFIG.general.units.format_size(magnet.bytes as i64);
         Row::new([
             Cell::from(Cow::Owned(magnet.seeders.to_string())).light_green(),
             Cell::from(Cow::Borrowed(&*magnet.title)),
@@ -130,11 +104,60 @@ impl SearchTab {
     }
 
     fn add_torrent(&mut self) {
-        let magnet_url = self.table.current_item().map(|magnet| magnet.url);
-        if let Some(magnet_url) = magnet_url {
-            self.ctx
-                .send_torrent_action(TorrentAction::Add(magnet_url, None));
+        let Some(magnet) = self.table.current_item() else {
+            return;
+        };
+
+        let add_action = TorrentAction::Add(magnet.url, None);
+        if magnet.seeders >= CONFIG.search_tab.low_seeders_threshold {
+            self.ctx.send_torrent_action(add_action);
+            return;
         }
+
+        // Providers don't report leechers, so seeders are all there is to show
+        let seeders = match magnet.seeders {
+            0 => "no seeders".to_string(),
+            1 => "only 1 seeder".to_string(),
+            count => format!("only {count} seeders"),
+        };
+        let lines = vec![
+            Line::from(magnet.title),
+            Line::default(),
+            Line::from(format!(
+                "It has {seeders}, so it may never finish downloading."
+            )),
+        ];
+        self.popup_manager
+            .show_confirm_popup("Few seeders", lines, "ADD ANYWAY", add_action);
+        self.ctx.send_action(Action::Render);
+    }
+
+    // Replaces the search that's running, if any, so its results don't mix
+    // with the ones for the new phrase.
+    fn restart_search(&mut self, delay: Duration) {
+        if let Some(handle) = self.search_task_handle.take() {
+            handle.abort();
+        }
+
+        let phrase = self.input.value().trim().to_string();
+        if phrase.is_empty() {
+            self.bottom_bar.search_state.reset();
+            return;
+        }
+
+        let providers = self
+            .configured_providers
+            .iter()
+            .filter(|configured_provider| configured_provider.enabled)
+            .map(|configured_provider| configured_provider.provider)
+            .collect();
+        self.search_task_handle = Some(tokio::task::spawn(search(
+            self.ctx.clone(),
+            self.client.clone(),
+            providers,
+            phrase,
+            delay,
+        )));
     }
 
     fn handle_input(&mut self, input: KeyEvent) {
@@ -142,7 +165,7 @@ impl SearchTab {
 
         match input.code {
             KeyCode::Enter => {
-                self.search_query_rx.send(self.input.to_string()).unwrap();
+                self.restart_search(Duration::ZERO);
                 self.focus = SearchTabFocus::List;
                 self.ctx
                     .send_update_action(UpdateAction::SwitchToNormalMode);
@@ -155,13 +178,33 @@ impl SearchTab {
             _ => {
                 let event = Event::Key(input);
                 if let Some(req) = to_input_request(&event) {
-                    self.input.handle(req);
+                    if self.input.handle(req).is_some_and(|change| change.value) {
+                        self.restart_search(SEARCH_DEBOUNCE);
+                    }
                     self.ctx.send_action(A::Render);
                 }
             }
         }
     }
 
+    fn paste(&mut self, text: &str) {
+        if self.focus != SearchTabFocus::Search {
+            return;
+        }
+
+        let mut changed = false;
+        for c in text.chars().filter(|c| !c.is_control()) {
+            changed |= self
+                .input
+                .handle(InputRequest::InsertChar(c))
+                .is_some_and(|change| change.value);
+        }
+        if changed {
+            self.restart_search(SEARCH_DEBOUNCE);
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
     fn start_search(&mut self) {
         self.focus = SearchTabFocus::Search;
         self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
@@ -244,6 +287,30 @@ impl SearchTab {
     }
 }
 
+async fn search(
+    ctx: app::Ctx,
+    client: Client,
+    providers: Vec<WhichProvider>,
+    phrase: String,
+    delay: Duration,
+) {
+    tokio::time::sleep(delay).await;
+
+    ctx.send_update_action(UpdateAction::SearchStarted);
+    let mut futures: FuturesUnordered<_> = providers
+        .iter()
+        .map(|provider| provider.search(&client, &phrase))
+        .collect();
+
+    while let Some(result) = futures.next().await {
+        match result {
+            Ok(response) => ctx.send_update_action(UpdateAction::ProviderResult(response)),
+            Err(e) => ctx.send_update_action(UpdateAction::ProviderError(e)),
+        }
+    }
+    ctx.send_update_action(UpdateAction::SearchFinished);
+}
+
 impl Component for SearchTab {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         use Action as A;
@@ -254,14 +321,14 @@ impl Component for SearchTab {
         }
 
         if action.is_quit() {
-            self.ctx.send_action(Action::HardQuit);
+            return ComponentAction::Quit;
         }
 
         match action {
-            A::Quit => self.ctx.send_action(Action::Quit),
             A::Search => self.start_search(),
             A::ChangeFocus => self.change_focus(),
             A::Input(input) => self.handle_input(input),
+            A::Paste(text) => self.paste(&text),
             A::Down => self.next_torrent(),
             A::Up => self.previous_torrent(),
             A::ScrollDownPage => self.scroll_down_page(),
@@ -301,7 +368,9 @@ impl Component for SearchTab {
                 self.update_providers_popup();
 
                 self.table.items.extend(response.magnets);
-                self.table.items.sort_by(|a, b| b.seeders.cmp(&a.seeders));
+                self.table
+                    .items
+                    .sort_by_key(|magnet| std::cmp::Reverse(magnet.seeders));
 
                 let mut state = self.table.state.borrow_mut();
                 if !self.table.items.is_empty() && state.selected().is_none() {
@@ -379,7 +448,7 @@ impl Component for SearchTab {
             search_rect.y,
         );
 
-        let header = Row::new(["S", "Title", "Size"]);
+        let header = Row::new(["S", "Title", "Size"]).style(CONFIG.theme.header_style());
 
         let table_items = &self.table.items;
 
@@ -392,10 +461,7 @@ impl Component for SearchTab {
             Constraint::Length(8),                                  // Size
         ];
 
-        let table_higlight_style = Style::default()
-            .on_black()
-            .bold()
-            .fg(CONFIG.general.accent_color);
+        let table_higlight_style = CONFIG.theme.selected_row_style();
 
         let table = {
             let table = Table::new(items, widths).highlight_style(table_higlight_style);
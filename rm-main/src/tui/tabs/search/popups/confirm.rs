@@ -0,0 +1,86 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use rm_config::CONFIG;
+use rm_shared::action::Action;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction},
+        main_window::centered_rect,
+    },
+};
+
+// Asks before doing something that's probably a mistake, doing it on confirm.
+pub struct ConfirmPopup {
+    ctx: app::Ctx,
+    title: &'static str,
+    lines: Vec<Line<'static>>,
+    confirm_label: &'static str,
+    on_confirm: Option<TorrentAction>,
+}
+
+impl ConfirmPopup {
+    pub fn new(
+        ctx: app::Ctx,
+        title: &'static str,
+        lines: Vec<Line<'static>>,
+        confirm_label: &'static str,
+        on_confirm: TorrentAction,
+    ) -> Self {
+        Self {
+            ctx,
+            title,
+            lines,
+            confirm_label,
+            on_confirm: Some(on_confirm),
+        }
+    }
+}
+
+impl Component for ConfirmPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Confirm => {
+                if let Some(on_confirm) = self.on_confirm.take() {
+                    self.ctx.send_torrent_action(on_confirm);
+                }
+                ComponentAction::Quit
+            }
+            _ if action.is_soft_quit() => ComponentAction::Quit,
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let centered_rect = centered_rect(rect, 50, 30);
+        let popup_rect = centered_rect.inner(Margin::new(1, 1));
+        let text_rect = popup_rect.inner(Margin::new(3, 2));
+        let button_rect = Layout::vertical([Constraint::Percentage(100), Constraint::Length(1)])
+            .split(text_rect)[1];
+
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+        let buttons = Line::from(vec![
+            Span::styled(format!("[ {} ]", self.confirm_label), accent_style.bold()),
+            Span::raw(" "),
+            Span::raw("[ CANCEL ]"),
+        ])
+        .right_aligned();
+
+        let block = Block::bordered()
+            .border_set(symbols::border::ROUNDED)
+            .title_style(accent_style)
+            .title(format!(" {} ", self.title));
+
+        let message = Paragraph::new(self.lines.clone()).wrap(Wrap { trim: false });
+
+        f.render_widget(Clear, centered_rect);
+        f.render_widget(block, popup_rect);
+        f.render_widget(message, text_rect);
+        f.render_widget(buttons, button_rect);
+    }
+}
@@ -1,10 +1,13 @@
+mod confirm;
 mod providers;
 
+use confirm::ConfirmPopup;
 use providers::ProvidersPopup;
 use ratatui::prelude::*;
 use ratatui::Frame;
 use rm_shared::action::Action;
 
+use crate::transmission::TorrentAction;
 use crate::tui::app;
 use crate::tui::components::Component;
 use crate::tui::components::ComponentAction;
@@ -18,6 +21,7 @@ pub struct PopupManager {
 
 pub enum CurrentPopup {
     Providers(ProvidersPopup),
+    Confirm(ConfirmPopup),
 }
 
 impl PopupManager {
@@ -40,6 +44,22 @@ impl PopupManager {
         self.show_popup(CurrentPopup::Providers(ProvidersPopup::new(providers)));
     }
 
+    pub fn show_confirm_popup(
+        &mut self,
+        title: &'static str,
+        lines: Vec<Line<'static>>,
+        confirm_label: &'static str,
+        on_confirm: TorrentAction,
+    ) {
+        self.show_popup(CurrentPopup::Confirm(ConfirmPopup::new(
+            self.ctx.clone(),
+            title,
+            lines,
+            confirm_label,
+            on_confirm,
+        )));
+    }
+
     pub fn close_popup(&mut self) {
         self.current_popup = None;
     }
@@ -48,13 +68,13 @@ impl PopupManager {
 impl Component for PopupManager {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         if let Some(current_popup) = &mut self.current_popup {
-            match current_popup {
-                CurrentPopup::Providers(popup) => {
-                    if popup.handle_actions(action).is_quit() {
-                        self.close_popup();
-                        self.ctx.send_action(Action::Render);
-                    }
-                }
+            let component_action = match current_popup {
+                CurrentPopup::Providers(popup) => popup.handle_actions(action),
+                CurrentPopup::Confirm(popup) => popup.handle_actions(action),
+            };
+            if component_action.is_quit() {
+                self.close_popup();
+                self.ctx.send_action(Action::Render);
             }
         }
 
@@ -65,6 +85,7 @@ impl Component for PopupManager {
         if let Some(popup) = &mut self.current_popup {
             match popup {
                 CurrentPopup::Providers(popup) => popup.render(f, rect),
+                CurrentPopup::Confirm(popup) => popup.render(f, rect),
             }
         }
     }
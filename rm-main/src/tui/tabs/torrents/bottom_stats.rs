@@ -1,16 +1,19 @@
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 use ratatui::{
-    layout::{Alignment, Rect},
-    widgets::Paragraph,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Span,
+    widgets::{Paragraph, Sparkline},
     Frame,
 };
-use rm_shared::utils::bytes_to_human_format;
+use rm_config::CONFIG;
+use throbber_widgets_tui::{Throbber, ThrobberState};
 use transmission_rpc::types::{FreeSpace, SessionStats};
 
 use crate::tui::components::Component;
 
-use super::table_manager::TableManager;
+use super::{table_manager::TableManager, tasks::default::DefaultBar};
 
 #[derive(Default)]
 pub(super) struct BottomStats {
@@ -19,7 +22,25 @@ pub(super) struct BottomStats {
     pub(super) stats: Option<Arc<SessionStats>>,
     pub(super) free_space: Option<Arc<FreeSpace>>,
     torrent_count: u16,
+    torrent_total_count: u16,
     torrent_currently_selected: u16,
+    reconnecting: bool,
+    label_filter: Option<String>,
+    selected_error: Option<String>,
+    selected_summary: Option<String>,
+    download_history: VecDeque<u64>,
+    upload_history: VecDeque<u64>,
+    totals_scope: TotalsScope,
+    // Spins while Transmission is busy with our requests
+    rpc_spinner: Option<ThrobberState>,
+}
+
+// Which transferred totals are shown next to the speeds
+#[derive(Default, Clone, Copy)]
+enum TotalsScope {
+    #[default]
+    Session,
+    AllTime,
 }
 
 impl BottomStats {
@@ -28,6 +49,17 @@ impl BottomStats {
     }
 
     pub fn set_stats(&mut self, stats: Arc<SessionStats>) {
+        let samples = CONFIG.torrents_tab.speed_graph_samples;
+        for (history, speed) in [
+            (&mut self.download_history, stats.download_speed),
+            (&mut self.upload_history, stats.upload_speed),
+        ] {
+            history.push_back(u64::try_from(speed).unwrap_or_default());
+            while history.len() > samples {
+                history.pop_front();
+            }
+        }
+
         self.stats = Some(stats);
     }
 
@@ -35,23 +67,97 @@ impl BottomStats {
         self.free_space = Some(free_space);
     }
 
+    pub fn toggle_totals_scope(&mut self) {
+        self.totals_scope = match self.totals_scope {
+            TotalsScope::Session => TotalsScope::AllTime,
+            TotalsScope::AllTime => TotalsScope::Session,
+        };
+    }
+
+    fn totals_text(&self, stats: &SessionStats) -> String {
+        let (scope, totals) = match self.totals_scope {
+            TotalsScope::Session => ("session", &stats.current_stats),
+            TotalsScope::AllTime => ("all time", &stats.cumulative_stats),
+        };
+
+        let units = CONFIG.general.units;
+        let downloaded = units.format_size(totals.downloaded_bytes);
+        let uploaded = units.format_size(totals.uploaded_bytes);
+        let ratio = if totals.downloaded_bytes == 0 {
+            "n/a".to_string()
+        } else {
+            format!(
+                "{:.2}",
+                totals.uploaded_bytes as f64 / totals.downloaded_bytes as f64
+            )
+        };
+
+        format!("{scope}: ↓ {downloaded} ↑ {uploaded} ratio {ratio}")
+    }
+
+    // Returns whether the spinner changed and has to be drawn again
+    pub fn tick_rpc_spinner(&mut self, rpc_calls_in_flight: usize) -> bool {
+        if rpc_calls_in_flight > 0 {
+            self.rpc_spinner
+                .get_or_insert_with(ThrobberState::default)
+                .calc_next();
+            true
+        } else {
+            self.rpc_spinner.take().is_some()
+        }
+    }
+
+    pub fn set_reconnecting(&mut self, reconnecting: bool) {
+        self.reconnecting = reconnecting;
+    }
+
     pub fn update_selected_indicator(&mut self, table_manager: &TableManager) {
         self.torrent_count = u16::try_from(table_manager.table.get_len()).unwrap();
+        self.torrent_total_count = u16::try_from(table_manager.table.items.len()).unwrap();
+        self.label_filter.clone_from(&table_manager.label_filter);
         if let Some(currently_selected) = table_manager.table.state.borrow().selected() {
             self.torrent_currently_selected = u16::try_from(currently_selected + 1).unwrap();
         }
+        let selected = table_manager.selected_torrent();
+        self.selected_error = selected.and_then(|torrent| {
+            let missing_fields = torrent.missing_fields();
+            torrent.error.clone().or_else(|| {
+                (!missing_fields.is_empty())
+                    .then(|| format!("Transmission didn't send: {}", missing_fields.join(", ")))
+            })
+        });
+        self.selected_summary = selected
+            .filter(|_| CONFIG.torrents_tab.show_selection_summary)
+            .map(|torrent| {
+                format!(
+                    "{} | ratio {} | {} peers | {}",
+                    torrent.torrent_name,
+                    torrent.upload_ratio,
+                    torrent.peers_connected,
+                    torrent.download_dir
+                )
+            });
     }
 }
 impl Component for BottomStats {
     fn render(&mut self, f: &mut Frame, rect: Rect) {
+        if self.reconnecting {
+            let paragraph = Paragraph::new("Reconnecting...")
+                .yellow()
+                .alignment(Alignment::Right);
+            f.render_widget(paragraph, rect);
+            return;
+        }
+
         if let Some(stats) = &self.stats {
-            let download = bytes_to_human_format(stats.download_speed);
-            let upload = bytes_to_human_format(stats.upload_speed);
+            let units = CONFIG.general.units;
+            let download = units.format_speed(stats.download_speed);
+            let upload = units.format_speed(stats.upload_speed);
 
-            let mut text = format!(" {download} |  {upload}");
+            let mut text = format!("{} |  {download} |  {upload}", self.totals_text(stats));
 
             if let Some(free_space) = &self.free_space {
-                let free_space = bytes_to_human_format(free_space.size_bytes);
+                let free_space = units.format_size(free_space.size_bytes);
                 text = format!("󰋊 {free_space} | {text}")
             }
 
@@ -65,8 +171,101 @@ impl Component for BottomStats {
                 text = format!(" {} | {text}", self.torrent_count);
             }
 
+            // let the user know that a filter hides some of the torrents
+            if self.torrent_count != self.torrent_total_count {
+                text = format!(
+                    "{} of {} torrents | {text}",
+                    self.torrent_count, self.torrent_total_count
+                );
+            }
+
+            if let Some(label) = &self.label_filter {
+                text = format!("label: {label} | {text}");
+            }
+
+            if let Some(state) = &self.rpc_spinner {
+                let spinner = Throbber::default().to_symbol_span(state);
+                text = format!("{}| {text}", spinner.content);
+            }
+
+            let text_width = u16::try_from(Span::raw(&text).width()).unwrap_or(u16::MAX);
             let paragraph = Paragraph::new(text).alignment(Alignment::Right);
             f.render_widget(paragraph, rect);
+
+            let mut used_width = text_width;
+            if CONFIG.torrents_tab.show_speed_graph {
+                used_width += self.render_speed_graph(f, rect, text_width);
+            }
+
+            // Whatever is left between the help hint and the stats
+            let hint_width = DefaultBar::help_hint().map_or(0, |hint| {
+                u16::try_from(Span::raw(hint).width() + 3).unwrap_or(0)
+            });
+            let left_rect = Rect {
+                x: rect.x + hint_width.min(rect.width),
+                width: rect.width.saturating_sub(used_width + hint_width + 1),
+                ..rect
+            };
+
+            if let Some(error) = &self.selected_error {
+                let paragraph = Paragraph::new(format!("⚠ {error}")).fg(CONFIG.theme.error);
+                f.render_widget(paragraph, left_rect);
+            } else if let Some(summary) = &self.selected_summary {
+                let paragraph = Paragraph::new(summary.as_str()).dark_gray();
+                f.render_widget(paragraph, left_rect);
+            }
         }
     }
 }
+
+impl BottomStats {
+    // Returns how wide the graph turned out, 0 if it didn't fit
+    fn render_speed_graph(&self, f: &mut Frame, rect: Rect, text_width: u16) -> u16 {
+        let samples = u16::try_from(CONFIG.torrents_tab.speed_graph_samples).unwrap_or(u16::MAX);
+        // "↓" + graph + " ↑" + graph + " | "
+        let graph_width = 2 * samples + 6;
+        if samples == 0 || graph_width + text_width > rect.width {
+            return 0;
+        }
+
+        let [_, download_label, download_rect, upload_label, upload_rect, _, _] =
+            Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(samples),
+                Constraint::Length(2),
+                Constraint::Length(samples),
+                Constraint::Length(3),
+                Constraint::Length(text_width),
+            ])
+            .areas(rect);
+
+        // Themes without download/seed colors get the accent color
+        let graph_style = |color| {
+            if color == Color::Reset {
+                Style::default().fg(CONFIG.general.accent_color)
+            } else {
+                Style::default().fg(color)
+            }
+        };
+        let download: Vec<u64> = self.download_history.iter().copied().collect();
+        let upload: Vec<u64> = self.upload_history.iter().copied().collect();
+
+        f.render_widget("↓", download_label);
+        f.render_widget(
+            Sparkline::default()
+                .data(&download)
+                .style(graph_style(CONFIG.theme.downloading)),
+            download_rect,
+        );
+        f.render_widget(" ↑", upload_label);
+        f.render_widget(
+            Sparkline::default()
+                .data(&upload)
+                .style(graph_style(CONFIG.theme.seeding)),
+            upload_rect,
+        );
+
+        graph_width
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use rm_shared::action::UpdateAction;
+use tokio::{sync::oneshot, task::JoinHandle};
+use transmission_rpc::types::{Id, Torrent};
+
+use crate::{transmission::TorrentAction, tui::app};
+
+// How long the selection has to stay on a torrent before its details are fetched
+const DEBOUNCE: Duration = Duration::from_millis(300);
+// How long fetched details are good enough to be shown right away
+const TTL: Duration = Duration::from_secs(10);
+
+// Details of the recently selected torrents, fetched in the background so
+// that the details popup has something to show as soon as it's opened.
+pub struct DetailsCache {
+    ctx: app::Ctx,
+    entries: HashMap<i64, (Instant, Torrent)>,
+    // The torrent that's selected and since when
+    candidate: Option<(i64, Instant)>,
+    prefetch_handle: Option<JoinHandle<()>>,
+}
+
+async fn prefetch(ctx: app::Ctx, id: i64) {
+    let (torrent_tx, torrent_rx) = oneshot::channel();
+    ctx.send_torrent_action(TorrentAction::GetTorrentsById(vec![Id::Id(id)], torrent_tx));
+
+    // Failures are left for the details popup to report
+    if let Ok(Ok(mut torrents)) = torrent_rx.await {
+        if let Some(torrent) = torrents.pop() {
+            ctx.send_update_action(UpdateAction::TorrentDetailsPrefetched(Box::new(torrent)));
+        }
+    }
+}
+
+impl DetailsCache {
+    pub fn new(ctx: app::Ctx) -> Self {
+        Self {
+            ctx,
+            entries: HashMap::new(),
+            candidate: None,
+            prefetch_handle: None,
+        }
+    }
+
+    // Called periodically with the currently selected torrent.
+    pub fn observe_selection(&mut self, selected_id: Option<i64>) {
+        self.entries
+            .retain(|_, (fetched_at, _)| fetched_at.elapsed() < TTL);
+
+        let Some(id) = selected_id else {
+            self.candidate = None;
+            return;
+        };
+
+        match self.candidate {
+            Some((candidate_id, since)) if candidate_id == id => {
+                if since.elapsed() >= DEBOUNCE && !self.is_pending() && self.get(id).is_none() {
+                    self.prefetch_handle = Some(tokio::spawn(prefetch(self.ctx.clone(), id)));
+                }
+            }
+            _ => {
+                self.candidate = Some((id, Instant::now()));
+                if let Some(handle) = self.prefetch_handle.take() {
+                    handle.abort();
+                }
+            }
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        self.prefetch_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    pub fn get(&self, id: i64) -> Option<&Torrent> {
+        self.entries
+            .get(&id)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < TTL)
+            .map(|(_, torrent)| torrent)
+    }
+
+    pub fn insert(&mut self, torrent: Torrent) {
+        if let Some(id) = torrent.id {
+            self.entries.insert(id, (Instant::now(), torrent));
+        }
+    }
+
+    // Forgets everything, for when torrents were changed
+    pub fn invalidate(&mut self) {
+        self.entries.clear();
+        if let Some(handle) = self.prefetch_handle.take() {
+            handle.abort();
+        }
+    }
+}
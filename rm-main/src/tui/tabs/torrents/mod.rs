@@ -1,26 +1,48 @@
 mod bottom_stats;
+mod details_cache;
 pub mod popups;
 pub mod rustmission_torrent;
 pub mod table_manager;
 pub mod task_manager;
 pub mod tasks;
+#[cfg(test)]
+mod test_utils;
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use crate::transmission::TorrentAction;
 use crate::tui::app;
+use crate::tui::clipboard;
 use crate::tui::components::{Component, ComponentAction};
 
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use popups::alt_speed::AltSpeedPopup;
+use popups::columns::ColumnsPopup;
+use popups::session_info::SessionInfoPopup;
+use popups::session_settings::SessionSettingsPopup;
 use popups::stats::StatisticsPopup;
+use popups::trackers::TrackersPopup;
+use ratatui::layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::{Row, Table};
-use rm_config::CONFIG;
-use rm_shared::status_task::StatusTask;
+use ratatui::widgets::{Paragraph, Table, TableState};
+use rm_config::{
+    state::{PausedUpload, State},
+    CONFIG,
+};
+use rm_shared::{header::Header, session::TorrentUploadLimit, status_task::StatusTask};
 use rustmission_torrent::RustmissionTorrent;
-use transmission_rpc::types::TorrentStatus;
+use tokio::sync::watch;
+use transmission_rpc::types::{Torrent, TorrentGetField, TorrentSetArgs, TorrentStatus};
 
 use crate::transmission;
 use rm_shared::action::{Action, ErrorMessage, UpdateAction};
 
 use self::bottom_stats::BottomStats;
+use self::details_cache::DetailsCache;
+use self::popups::details::DetailsPopup;
 use self::popups::files::FilesPopup;
 use self::popups::{CurrentPopup, PopupManager};
 use self::table_manager::TableManager;
@@ -32,22 +54,63 @@ pub struct TorrentsTab {
     popup_manager: PopupManager,
     task_manager: TaskManager,
     bottom_stats: BottomStats,
+    details_cache: DetailsCache,
+    last_click: Option<(Instant, usize)>,
+    removed_torrents: Option<(Instant, Vec<Torrent>)>,
+    // Fields the torrents fetcher asks for, following the columns and popups shown
+    torrent_fields_tx: watch::Sender<Vec<TorrentGetField>>,
+    // Whether torrents were fetched at least once, to tell "none yet" from "none at all"
+    torrents_loaded: bool,
+    // Latest upload limits by torrent ID
+    upload_limits: HashMap<i64, TorrentUploadLimit>,
+    // When uploading of torrents got paused or resumed, so that limits fetched
+    // before that don't undo it on screen
+    upload_toggled_at: HashMap<i64, Instant>,
+    // When torrents finished downloading, for those that did since we've been watching
+    completed_at: HashMap<i64, Instant>,
+    // Fields Transmission didn't send that were already reported
+    reported_missing_fields: HashSet<&'static str>,
 }
 
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+const UNDO_REMOVAL_WINDOW: Duration = Duration::from_secs(5);
+
 impl TorrentsTab {
     pub fn new(ctx: app::Ctx) -> Self {
         let table_manager = TableManager::new();
         let bottom_stats = BottomStats::new();
 
-        tokio::spawn(transmission::fetchers::stats(ctx.clone()));
-        tokio::spawn(transmission::fetchers::torrents(ctx.clone()));
-        tokio::spawn(transmission::fetchers::free_space(ctx.clone()));
+        let connection = transmission::fetchers::Connection::default();
+        tokio::spawn(transmission::fetchers::stats(
+            ctx.clone(),
+            connection.clone(),
+        ));
+        let (torrent_fields_tx, torrent_fields_rx) =
+            watch::channel(table_manager.torrent_fields(false));
+        tokio::spawn(transmission::fetchers::torrents(
+            ctx.clone(),
+            connection.clone(),
+            torrent_fields_rx,
+        ));
+        tokio::spawn(transmission::fetchers::free_space(ctx.clone(), connection));
+        for feed in &CONFIG.rss_feeds {
+            tokio::spawn(transmission::fetchers::rss_feed(ctx.clone(), feed));
+        }
 
         Self {
             bottom_stats,
             task_manager: TaskManager::new(ctx.clone()),
             table_manager,
             popup_manager: PopupManager::new(ctx.clone()),
+            details_cache: DetailsCache::new(ctx.clone()),
+            last_click: None,
+            removed_torrents: None,
+            torrent_fields_tx,
+            torrents_loaded: false,
+            upload_limits: HashMap::new(),
+            upload_toggled_at: HashMap::new(),
+            completed_at: HashMap::new(),
+            reported_missing_fields: HashSet::new(),
             ctx,
         }
     }
@@ -76,10 +139,25 @@ impl Component for TorrentsTab {
         }
 
         if action.is_quit() {
-            self.ctx.send_action(Action::HardQuit);
+            return ComponentAction::Quit;
+        }
+
+        if matches!(
+            action,
+            A::Pause
+                | A::CyclePriority
+                | A::StartAll
+                | A::StartNow
+                | A::StartNowAll
+                | A::ToggleUpload
+                | A::PauseAll
+                | A::VerifyAll
+        ) {
+            self.details_cache.invalidate();
         }
 
         match action {
+            A::Mouse(mouse) => self.handle_mouse(mouse),
             A::Up => self.previous_torrent(),
             A::Down => self.next_torrent(),
             A::ScrollUpPage => self.scroll_page_up(),
@@ -88,27 +166,82 @@ impl Component for TorrentsTab {
             A::End => self.scroll_to_end(),
             A::ShowStats => self.show_statistics_popup(),
             A::ShowFiles => self.show_files_popup(),
+            A::ShowDetails => self.show_details_popup(),
+            A::ShowSessionSettings => self.show_session_settings_popup(),
+            A::ShowSessionInfo => self.show_session_info_popup(),
+            A::ShowColumns => self.show_columns_popup(),
+            A::ShowTrackerGroups => self.show_trackers_popup(),
+            A::ShowAltSpeedSchedule => self.show_alt_speed_popup(),
+            A::CycleLabelFilter => self.cycle_label_filter(),
+            A::SortNextColumn => {
+                self.table_manager.sort_by_next_column();
+                self.ctx.send_action(Action::Render);
+            }
+            A::NextSortPreset => {
+                let preset_name = self.table_manager.next_sort_preset();
+                self.ctx
+                    .send_update_action(UpdateAction::TaskSetSuccess(StatusTask::new_sort(
+                        preset_name,
+                    )));
+                self.ctx.send_action(Action::Render);
+            }
+            A::ReverseSort => {
+                self.table_manager.reverse_sort();
+                self.ctx.send_action(Action::Render);
+            }
+            A::UndoRemoval => self.undo_removal(),
             A::Pause => self.pause_current_torrent(),
+            A::CyclePriority => self.cycle_current_torrent_priority(),
+            A::StartAll => self.start_all_torrents(),
+            A::StartNow => self.start_current_torrent_now(),
+            A::StartNowAll => self.start_all_queued_torrents_now(),
+            A::ToggleUpload => self.toggle_current_torrent_upload(),
+            A::PauseAll => self.pause_all_torrents(),
+            A::VerifyAll => self.verify_all_torrents(),
+            A::LabelAll => self.label_all_torrents(),
+            A::Export => {
+                let records = self
+                    .table_manager
+                    .visible_torrents()
+                    .into_iter()
+                    .map(|torrent| (&*torrent).into())
+                    .collect();
+                self.task_manager.export(records);
+            }
             A::DeleteWithFiles => {
-                if let Some(torrent) = self.table_manager.current_torrent() {
+                if let Some(torrent) = self.table_manager.selected_torrent() {
                     self.task_manager
                         .delete_torrent(torrent, tasks::delete_torrent::Mode::WithFiles);
                 }
             }
             A::DeleteWithoutFiles => {
-                if let Some(torrent) = self.table_manager.current_torrent() {
+                if let Some(torrent) = self.table_manager.selected_torrent() {
                     self.task_manager
                         .delete_torrent(torrent, tasks::delete_torrent::Mode::WithoutFiles);
                 }
             }
             A::AddMagnet => self.task_manager.add_magnet(),
-            A::Search => self.task_manager.search(&self.table_manager.filter),
+            A::Search => self
+                .task_manager
+                .search(&self.table_manager.filter, self.table_manager.case_matching),
+            A::QuickJump => self.task_manager.quick_jump(),
             A::MoveTorrent => {
-                if let Some(torrent) = self.table_manager.current_torrent() {
+                if let Some(torrent) = self.table_manager.selected_torrent() {
                     self.task_manager.move_torrent(torrent);
                 }
             }
             A::XdgOpen => self.open_current_torrent(),
+            A::OpenDownloadDir => self.open_current_torrent_dir(),
+            A::CopyHash => self.copy_current_torrent_hash(),
+            A::ResetView => self.reset_view(),
+            A::ToggleRowLayout => {
+                self.table_manager.toggle_row_layout();
+                self.ctx.send_action(Action::Render);
+            }
+            A::ToggleTotals => {
+                self.bottom_stats.toggle_totals_scope();
+                self.ctx.send_action(Action::Render);
+            }
             other => {
                 self.task_manager.handle_actions(other);
             }
@@ -122,6 +255,8 @@ impl Component for TorrentsTab {
             UpdateAction::SessionStats(stats) => {
                 self.bottom_stats.set_stats(stats);
             }
+            UpdateAction::ConnectionLost => self.bottom_stats.set_reconnecting(true),
+            UpdateAction::ConnectionRestored => self.bottom_stats.set_reconnecting(false),
             UpdateAction::FreeSpace(free_space) => {
                 self.bottom_stats.set_free_space(free_space);
             }
@@ -132,61 +267,218 @@ impl Component for TorrentsTab {
                 self.bottom_stats
                     .update_selected_indicator(&self.table_manager);
             }
+            UpdateAction::SearchFilterCaseMatching(case_matching) => {
+                self.table_manager.case_matching = case_matching;
+                State::remember_case_matching(case_matching);
+            }
             UpdateAction::SearchFilterClear => {
-                self.table_manager.filter = None;
+                self.table_manager.clear_filter();
                 self.table_manager.table.state.borrow_mut().select(Some(0));
                 self.table_manager.update_rows_number();
                 self.bottom_stats
                     .update_selected_indicator(&self.table_manager);
             }
-            UpdateAction::UpdateTorrents(torrents) => {
-                let torrents = torrents.into_iter().map(RustmissionTorrent::from).collect();
+            UpdateAction::QuickJump(pattern) => {
+                self.table_manager.jump_to(&pattern);
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+            }
+            UpdateAction::UpdateTorrents(torrents, fields) => {
+                let requested: HashSet<String> =
+                    fields.iter().map(TorrentGetField::to_str).collect();
+                let torrents: Vec<RustmissionTorrent> = torrents
+                    .into_iter()
+                    .map(|torrent| RustmissionTorrent::new(torrent, &requested))
+                    .collect();
+                self.report_missing_fields(&torrents);
+                self.note_completions(&torrents);
                 self.table_manager.set_new_rows(torrents);
+                self.expire_completions();
+                self.apply_completed_highlight();
+                self.apply_upload_limits();
+                self.torrents_loaded = true;
+                self.popup_manager.update_torrents(
+                    &self.table_manager.table.items,
+                    requested.contains("trackers"),
+                );
                 self.bottom_stats
                     .update_selected_indicator(&self.table_manager);
             }
-            UpdateAction::UpdateCurrentTorrent(_) => {
-                self.popup_manager.handle_update_action(action)
+            UpdateAction::RefreshTorrents => {
+                self.torrent_fields_tx.send_modify(|_| ());
+            }
+            UpdateAction::UploadLimits(limits, requested_at) => {
+                self.set_upload_limits(limits, requested_at);
+                self.apply_upload_limits();
+            }
+            UpdateAction::TorrentsRemoved(torrents) => {
+                self.removed_torrents = Some((Instant::now(), torrents));
+            }
+            UpdateAction::TorrentDuplicate(torrent) => self.show_duplicate(&torrent),
+            UpdateAction::SetColumns(columns) => self.set_columns(columns),
+            UpdateAction::TorrentDetailsPrefetched(torrent) => self.details_cache.insert(*torrent),
+            UpdateAction::UpdateCurrentTorrent(ref torrent) => {
+                self.details_cache.insert(*torrent.clone());
+                self.popup_manager.handle_update_action(action);
+            }
+            UpdateAction::SessionSettings(_)
+            | UpdateAction::SessionInfo(_)
+            | UpdateAction::AltSpeedSchedule(_)
+            | UpdateAction::EffectiveLimits(_) => self.popup_manager.handle_update_action(action),
+            other => {
+                // Moving, labeling and such finished, so the details may be outdated
+                if matches!(other, UpdateAction::TaskSuccess) {
+                    self.details_cache.invalidate();
+                }
+                self.task_manager.handle_update_action(other);
             }
-            other => self.task_manager.handle_update_action(other),
         }
     }
 
     fn tick(&mut self) {
         self.task_manager.tick();
+        let torrent_fields = self
+            .table_manager
+            .torrent_fields(self.popup_manager.is_showing_trackers());
+        self.torrent_fields_tx.send_if_modified(|fields| {
+            let names = |fields: &[TorrentGetField]| -> Vec<String> {
+                fields.iter().map(TorrentGetField::to_str).collect()
+            };
+            let modified = names(fields) != names(&torrent_fields);
+            if modified {
+                *fields = torrent_fields;
+            }
+            modified
+        });
+        if self
+            .bottom_stats
+            .tick_rpc_spinner(self.ctx.rpc_calls_in_flight())
+        {
+            self.ctx.send_action(Action::Render);
+        }
+        let selected_id = self
+            .table_manager
+            .selected_torrent()
+            .map(|torrent| torrent.id_number());
+        self.details_cache.observe_selection(selected_id);
+        if self.expire_completions() {
+            self.apply_completed_highlight();
+            self.ctx.send_action(Action::Render);
+        }
     }
 }
 
 impl TorrentsTab {
+    pub fn has_active_downloads(&self) -> bool {
+        self.table_manager
+            .table
+            .items
+            .iter()
+            .any(|torrent| torrent.status() == TorrentStatus::Downloading)
+    }
+
+    fn show_duplicate(&mut self, torrent: &Torrent) {
+        let name = torrent.name.clone().unwrap_or_default();
+        let mut task = StatusTask::new_duplicate(name);
+        if let Some(id) = torrent.id {
+            task = if self.table_manager.select_by_id(id) {
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+                task.with_success_hint("selected it")
+            } else {
+                task.with_success_hint("hidden by the current filter")
+            };
+        }
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
     fn render_table(&mut self, f: &mut Frame, rect: Rect) {
-        self.table_manager.torrents_displaying_no = rect.height;
+        let row_height = self.table_manager.row_height();
+        self.table_manager.torrents_displaying_no = rect.height / row_height;
+        self.table_manager.table_rect = rect;
+        self.table_manager.set_available_width(rect.width);
+
+        let highlight_table_style = CONFIG.theme.selected_row_style();
 
-        let highlight_table_style = Style::default()
-            .on_black()
-            .bold()
-            .fg(CONFIG.general.accent_color);
+        let header_height = u16::from(!CONFIG.general.headers_hide);
+        let visible_range = self.table_manager.table.visible_range(usize::from(
+            rect.height.saturating_sub(header_height) / row_height,
+        ));
+
+        // The table only gets the visible rows, so its state has to be relative to them
+        let mut table_state = TableState::default();
+        if let Some(selected) = self.table_manager.table.state.borrow().selected() {
+            table_state.select(selected.checked_sub(visible_range.start));
+        }
 
         let table_widget = {
-            let table = Table::new(self.table_manager.rows(), &self.table_manager.widths)
-                .highlight_style(highlight_table_style);
+            let table = Table::new(
+                self.table_manager.rows(visible_range),
+                self.table_manager.table_widths(),
+            )
+            .highlight_style(highlight_table_style);
             if !CONFIG.general.headers_hide {
-                table.header(Row::new(self.table_manager.headers().iter().cloned()))
+                table.header(
+                    self.table_manager
+                        .header_row()
+                        .style(CONFIG.theme.header_style()),
+                )
             } else {
                 table
             }
         };
 
-        f.render_stateful_widget(
-            table_widget,
-            rect,
-            &mut self.table_manager.table.state.borrow_mut(),
-        );
+        f.render_stateful_widget(table_widget, rect, &mut table_state);
+
+        if let Some(message) = self.empty_table_message() {
+            let [_, message_rect] =
+                Layout::vertical([Constraint::Length(header_height), Constraint::Fill(1)])
+                    .areas(rect);
+            let [message_rect] = Layout::vertical([Constraint::Length(1)])
+                .flex(Flex::Center)
+                .areas(message_rect);
+            f.render_widget(Paragraph::new(message).centered(), message_rect);
+        }
+    }
+
+    // Shown in place of the rows when there are none
+    fn empty_table_message(&self) -> Option<String> {
+        if !self.torrents_loaded {
+            return Some("Connecting…".to_string());
+        }
+        if !self.table_manager.table.items.is_empty() {
+            return None;
+        }
+
+        match CONFIG.keybindings.get_keys_for_action(Action::AddMagnet) {
+            Some(keys) => Some(format!("No torrents — press {keys} to add one")),
+            None => Some("No torrents".to_string()),
+        }
     }
 
     fn show_files_popup(&mut self) {
-        if let Some(highlighted_torrent) = self.table_manager.current_torrent() {
-            let popup = FilesPopup::new(self.ctx.clone(), highlighted_torrent.id.clone());
-            self.popup_manager.show_popup(CurrentPopup::Files(popup));
+        if let Some(highlighted_torrent) = self.table_manager.selected_torrent() {
+            let cached = self
+                .details_cache
+                .get(highlighted_torrent.id_number())
+                .cloned();
+            let popup = FilesPopup::new(self.ctx.clone(), highlighted_torrent.id.clone(), cached);
+            self.popup_manager
+                .show_popup(CurrentPopup::Files(Box::new(popup)));
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
+    fn show_details_popup(&mut self) {
+        if let Some(highlighted_torrent) = self.table_manager.selected_torrent() {
+            let cached = self
+                .details_cache
+                .get(highlighted_torrent.id_number())
+                .cloned();
+            let popup = DetailsPopup::new(self.ctx.clone(), highlighted_torrent.id.clone(), cached);
+            self.popup_manager
+                .show_popup(CurrentPopup::Details(Box::new(popup)));
             self.ctx.send_action(Action::Render);
         }
     }
@@ -199,6 +491,121 @@ impl TorrentsTab {
         }
     }
 
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.previous_torrent(),
+            MouseEventKind::ScrollDown => self.next_torrent(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(idx) = self.table_manager.row_at(mouse.column, mouse.row) else {
+                    return;
+                };
+
+                let is_double_click = self.last_click.is_some_and(|(clicked_at, clicked_idx)| {
+                    clicked_idx == idx && clicked_at.elapsed() < DOUBLE_CLICK_INTERVAL
+                });
+
+                self.table_manager
+                    .table
+                    .state
+                    .borrow_mut()
+                    .select(Some(idx));
+                self.bottom_stats
+                    .update_selected_indicator(&self.table_manager);
+
+                if is_double_click {
+                    self.last_click = None;
+                    self.show_details_popup();
+                } else {
+                    self.last_click = Some((Instant::now(), idx));
+                    self.ctx.send_action(Action::Render);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn undo_removal(&mut self) {
+        let Some((removed_at, torrents)) = self.removed_torrents.take() else {
+            return;
+        };
+        if removed_at.elapsed() > UNDO_REMOVAL_WINDOW {
+            return;
+        }
+
+        let mut names = vec![];
+        for torrent in torrents {
+            let Some(magnet) = magnet_link(&torrent) else {
+                continue;
+            };
+            self.ctx
+                .send_torrent_action(TorrentAction::Add(magnet, torrent.download_dir));
+            names.push(torrent.name.unwrap_or_default());
+        }
+
+        if names.is_empty() {
+            return;
+        }
+
+        self.ctx.send_update_action(UpdateAction::TaskClear);
+        let task = StatusTask::new_add(names.join(", "));
+        self.ctx.send_update_action(UpdateAction::TaskSet(task));
+    }
+
+    fn reset_view(&mut self) {
+        if self.table_manager.reset_view() {
+            self.bottom_stats
+                .update_selected_indicator(&self.table_manager);
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
+    fn cycle_label_filter(&mut self) {
+        self.table_manager.cycle_label_filter();
+        self.table_manager.table.state.borrow_mut().select(Some(0));
+        self.table_manager.update_rows_number();
+        self.bottom_stats
+            .update_selected_indicator(&self.table_manager);
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn show_session_settings_popup(&mut self) {
+        let popup = SessionSettingsPopup::new(self.ctx.clone());
+        self.popup_manager
+            .show_popup(CurrentPopup::SessionSettings(popup));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn show_session_info_popup(&mut self) {
+        let popup = SessionInfoPopup::new(self.ctx.clone());
+        self.popup_manager
+            .show_popup(CurrentPopup::SessionInfo(popup));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn show_columns_popup(&mut self) {
+        let popup = ColumnsPopup::new(self.ctx.clone(), self.table_manager.columns());
+        self.popup_manager.show_popup(CurrentPopup::Columns(popup));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn show_alt_speed_popup(&mut self) {
+        let popup = AltSpeedPopup::new(self.ctx.clone());
+        self.popup_manager.show_popup(CurrentPopup::AltSpeed(popup));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn show_trackers_popup(&mut self) {
+        let popup = TrackersPopup::new(self.ctx.clone());
+        self.popup_manager.show_popup(CurrentPopup::Trackers(popup));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn set_columns(&mut self, columns: Vec<Header>) {
+        State::remember_columns(columns.clone());
+        self.table_manager.set_columns(columns);
+        self.ctx.send_action(Action::Render);
+    }
+
     fn previous_torrent(&mut self) {
         self.table_manager.table.previous();
         self.bottom_stats
@@ -244,7 +651,7 @@ impl TorrentsTab {
     }
 
     fn pause_current_torrent(&mut self) {
-        if let Some(torrent) = self.table_manager.current_torrent() {
+        if let Some(torrent) = self.table_manager.selected_torrent_mut() {
             let torrent_id = torrent.id.clone();
             match torrent.status() {
                 TorrentStatus::Stopped => {
@@ -263,8 +670,335 @@ impl TorrentsTab {
         }
     }
 
+    // Reports fields Transmission didn't send, each one only once since
+    // it will most likely keep not sending them on every refresh
+    fn report_missing_fields(&mut self, torrents: &[RustmissionTorrent]) {
+        let mut newly_missing = vec![];
+        for torrent in torrents {
+            for field in torrent.missing_fields() {
+                if self.reported_missing_fields.insert(field) {
+                    newly_missing.push(*field);
+                }
+            }
+        }
+        if newly_missing.is_empty() {
+            return;
+        }
+
+        let err_message = ErrorMessage {
+            title: "Incomplete torrents".to_string(),
+            description: "Transmission didn't send some fields, they're shown as \"?\"".to_string(),
+            source: format!("Missing fields: {}", newly_missing.join(", ")),
+        };
+        self.ctx
+            .send_update_action(UpdateAction::Error(Box::new(err_message)));
+    }
+
+    // Compares the fresh torrents with the ones shown so far, remembering
+    // the ones that just finished
+    fn note_completions(&mut self, torrents: &[RustmissionTorrent]) {
+        if CONFIG.torrents_tab.completed_highlight_secs == 0 || !self.torrents_loaded {
+            return;
+        }
+
+        let incomplete: HashSet<i64> = self
+            .table_manager
+            .table
+            .items
+            .iter()
+            .filter(|torrent| !torrent.is_complete())
+            .map(RustmissionTorrent::id_number)
+            .collect();
+        for torrent in torrents {
+            if torrent.is_complete() && incomplete.contains(&torrent.id_number()) {
+                self.completed_at
+                    .insert(torrent.id_number(), Instant::now());
+            }
+        }
+    }
+
+    // Forgets the completions that aren't recent anymore, returns whether there were any
+    fn expire_completions(&mut self) -> bool {
+        let highlight_duration = Duration::from_secs(CONFIG.torrents_tab.completed_highlight_secs);
+        let completions_count = self.completed_at.len();
+        self.completed_at
+            .retain(|_, completed_at| completed_at.elapsed() < highlight_duration);
+        self.completed_at.len() != completions_count
+    }
+
+    fn apply_completed_highlight(&mut self) {
+        for torrent in &mut self.table_manager.table.items {
+            torrent.set_recently_completed(self.completed_at.contains_key(&torrent.id_number()));
+        }
+    }
+
+    fn set_upload_limits(&mut self, limits: Vec<TorrentUploadLimit>, requested_at: Instant) {
+        self.upload_toggled_at
+            .retain(|_, toggled_at| *toggled_at > requested_at);
+
+        let mut upload_limits: HashMap<i64, TorrentUploadLimit> =
+            limits.into_iter().map(|limit| (limit.id, limit)).collect();
+        // These were asked for before the toggle got through, so the toggle wins
+        for id in self.upload_toggled_at.keys() {
+            if let Some(limit) = self.upload_limits.get(id) {
+                upload_limits.insert(*id, *limit);
+            }
+        }
+        self.upload_limits = upload_limits;
+    }
+
+    fn apply_upload_limits(&mut self) {
+        for torrent in &mut self.table_manager.table.items {
+            torrent.upload_paused = self
+                .upload_limits
+                .get(&torrent.id_number())
+                .is_some_and(TorrentUploadLimit::is_upload_paused);
+        }
+    }
+
+    // Pauses uploading by limiting it to 0, or puts back the limit it had before
+    fn toggle_current_torrent_upload(&mut self) {
+        let Some(torrent) = self.table_manager.selected_torrent_mut() else {
+            return;
+        };
+
+        let id = torrent.id_number();
+        let limit = if torrent.upload_paused {
+            // Without a recorded limit, limiting just gets turned off
+            let paused = State::take_paused_upload(&torrent.hash)
+                .filter(|paused| !(paused.upload_limited && paused.upload_limit == 0));
+            match paused {
+                Some(paused) => TorrentUploadLimit {
+                    id,
+                    upload_limit: paused.upload_limit,
+                    upload_limited: paused.upload_limited,
+                },
+                None => TorrentUploadLimit {
+                    id,
+                    upload_limit: 0,
+                    upload_limited: false,
+                },
+            }
+        } else {
+            if let Some(limit) = self.upload_limits.get(&id) {
+                State::remember_paused_upload(PausedUpload {
+                    hash: torrent.hash.clone(),
+                    upload_limit: limit.upload_limit,
+                    upload_limited: limit.upload_limited,
+                });
+            }
+            TorrentUploadLimit {
+                id,
+                upload_limit: 0,
+                upload_limited: true,
+            }
+        };
+
+        // A limit of 0 that isn't turned on is what's there when nothing was set
+        let args = TorrentSetArgs {
+            upload_limit: Some(limit.upload_limit)
+                .filter(|upload_limit| limit.upload_limited || *upload_limit != 0),
+            upload_limited: Some(limit.upload_limited),
+            ..Default::default()
+        };
+        self.ctx.send_torrent_action(TorrentAction::SetArgs(
+            Box::new(args),
+            Some(vec![torrent.id.clone()]),
+        ));
+        torrent.upload_paused = limit.is_upload_paused();
+        self.upload_limits.insert(id, limit);
+        self.upload_toggled_at.insert(id, Instant::now());
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn cycle_current_torrent_priority(&mut self) {
+        if let Some(torrent) = self.table_manager.selected_torrent_mut() {
+            let priority = torrent.next_priority();
+            self.ctx
+                .send_torrent_action(TorrentAction::SetBandwidthPriority {
+                    ids: vec![torrent.id.clone()],
+                    priority: priority.clone(),
+                });
+            torrent.bandwidth_priority = priority;
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
+    fn open_current_torrent_dir(&mut self) {
+        if !CONFIG.torrents_tab.open_in_file_manager {
+            return;
+        }
+
+        let Some(torrent) = self.table_manager.selected_torrent() else {
+            return;
+        };
+
+        // Single-file torrents don't have their own folder
+        let torrent_location = torrent.torrent_location();
+        let dir = if Path::new(&torrent_location).is_dir() {
+            torrent_location
+        } else {
+            torrent.download_dir.clone()
+        };
+
+        let result = if Path::new(&dir).exists() {
+            open::that_detached(&dir)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{dir} does not exist"),
+            ))
+        };
+
+        match result {
+            Ok(()) => self
+                .ctx
+                .send_update_action(UpdateAction::TaskSetSuccess(StatusTask::new_open(dir))),
+            Err(err) => {
+                let desc = format!("Encountered an error while trying to open \"{}\"", dir);
+                let err_msg =
+                    ErrorMessage::new("Failed to open a torrent directory", desc, Box::new(err));
+                self.ctx
+                    .send_update_action(UpdateAction::Error(Box::new(err_msg)));
+            }
+        }
+    }
+
+    fn start_all_torrents(&mut self) {
+        let mut ids = vec![];
+        for torrent in self.table_manager.visible_torrents() {
+            if torrent.status() == TorrentStatus::Stopped {
+                ids.push(torrent.id.clone());
+                torrent.update_status(TorrentStatus::Downloading);
+            }
+        }
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let task = StatusTask::new_start(torrents_count_str(ids.len()));
+        self.ctx.send_torrent_action(TorrentAction::Start(ids));
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn start_current_torrent_now(&mut self) {
+        let Some(torrent) = self.table_manager.selected_torrent_mut() else {
+            return;
+        };
+
+        let Some(status) = started_now_status(torrent.status()) else {
+            return;
+        };
+
+        let task = StatusTask::new_start_now(torrent.torrent_name.clone());
+        self.ctx
+            .send_torrent_action(TorrentAction::StartNow(vec![torrent.id.clone()]));
+        torrent.update_status(status);
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn start_all_queued_torrents_now(&mut self) {
+        let mut ids = vec![];
+        for torrent in self.table_manager.visible_torrents() {
+            // Only the queued ones, stopped torrents were stopped on purpose
+            if torrent.status() == TorrentStatus::Stopped {
+                continue;
+            }
+            if let Some(status) = started_now_status(torrent.status()) {
+                ids.push(torrent.id.clone());
+                torrent.update_status(status);
+            }
+        }
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let task = StatusTask::new_start_now(torrents_count_str(ids.len()));
+        self.ctx.send_torrent_action(TorrentAction::StartNow(ids));
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn pause_all_torrents(&mut self) {
+        let mut ids = vec![];
+        for torrent in self.table_manager.visible_torrents() {
+            if torrent.status() != TorrentStatus::Stopped {
+                ids.push(torrent.id.clone());
+                torrent.update_status(TorrentStatus::Stopped);
+            }
+        }
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let task = StatusTask::new_pause(torrents_count_str(ids.len()));
+        self.ctx.send_torrent_action(TorrentAction::Stop(ids));
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn verify_all_torrents(&mut self) {
+        let ids: Vec<_> = self
+            .table_manager
+            .visible_torrents()
+            .into_iter()
+            .map(|torrent| torrent.id.clone())
+            .collect();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let task = StatusTask::new_verify(torrents_count_str(ids.len()));
+        self.ctx.send_torrent_action(TorrentAction::Verify(ids));
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn label_all_torrents(&mut self) {
+        let torrents: Vec<_> = self
+            .table_manager
+            .visible_torrents()
+            .into_iter()
+            .map(|torrent| (torrent.id.clone(), torrent.labels.clone()))
+            .collect();
+
+        if !torrents.is_empty() {
+            self.task_manager.label_torrents(torrents);
+        }
+    }
+
+    fn copy_current_torrent_hash(&mut self) {
+        let Some(torrent) = self.table_manager.selected_torrent() else {
+            return;
+        };
+
+        match clipboard::copy_to_clipboard(&torrent.hash) {
+            Ok(()) => {
+                self.ctx
+                    .send_update_action(UpdateAction::TaskSetSuccess(StatusTask::new_copy(
+                        format!("the hash of {}", torrent.torrent_name),
+                    )))
+            }
+            Err(err) => {
+                let desc = format!(
+                    "Couldn't copy the info hash of \"{}\"",
+                    torrent.torrent_name
+                );
+                let err_msg = ErrorMessage::new("Failed to copy the info hash", desc, err.into());
+                self.ctx
+                    .send_update_action(UpdateAction::Error(Box::new(err_msg)));
+            }
+        }
+    }
+
     fn open_current_torrent(&mut self) {
-        if let Some(torrent) = self.table_manager.current_torrent() {
+        if let Some(torrent) = self.table_manager.selected_torrent() {
             let torrent_location = torrent.torrent_location();
             match open::that_detached(&torrent_location) {
                 Ok(()) => {
@@ -290,3 +1024,39 @@ impl TorrentsTab {
         }
     }
 }
+
+// What a torrent becomes once started bypassing the queue, if it isn't running already
+const fn started_now_status(status: TorrentStatus) -> Option<TorrentStatus> {
+    match status {
+        TorrentStatus::Stopped | TorrentStatus::QueuedToDownload => {
+            Some(TorrentStatus::Downloading)
+        }
+        TorrentStatus::QueuedToSeed => Some(TorrentStatus::Seeding),
+        _ => None,
+    }
+}
+
+fn torrents_count_str(count: usize) -> String {
+    if count == 1 {
+        "1 torrent".to_string()
+    } else {
+        format!("{count} torrents")
+    }
+}
+
+// The .torrent file is gone once a torrent is removed, so a magnet link
+// (with the old trackers) is the only way to add it back.
+fn magnet_link(torrent: &Torrent) -> Option<String> {
+    let hash = torrent.hash_string.as_ref()?;
+    let mut params = vec![];
+    if let Some(name) = &torrent.name {
+        params.push(("dn", name.as_str()));
+    }
+    for tracker in torrent.trackers.iter().flatten() {
+        params.push(("tr", tracker.announce.as_str()));
+    }
+
+    reqwest::Url::parse_with_params(&format!("magnet:?xt=urn:btih:{hash}"), params)
+        .ok()
+        .map(String::from)
+}
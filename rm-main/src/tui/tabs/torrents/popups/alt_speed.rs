@@ -0,0 +1,333 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, Paragraph,
+    },
+};
+use rm_config::CONFIG;
+use tokio::{sync::oneshot, task::JoinHandle};
+use transmission_rpc::types::SessionSetArgs;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction},
+        main_window::centered_rect,
+    },
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    session::AltSpeedSchedule,
+};
+
+// Monday first, with the bit Transmission uses for each day (Sunday is 1)
+const DAYS: [(&str, i32); 7] = [
+    ("Monday", 1 << 1),
+    ("Tuesday", 1 << 2),
+    ("Wednesday", 1 << 3),
+    ("Thursday", 1 << 4),
+    ("Friday", 1 << 5),
+    ("Saturday", 1 << 6),
+    ("Sunday", 1 << 0),
+];
+
+pub struct AltSpeedPopup {
+    ctx: app::Ctx,
+    schedule: Option<ScheduleForm>,
+    selected: usize,
+    error: Option<String>,
+    schedule_task_handle: JoinHandle<()>,
+}
+
+struct ScheduleForm {
+    enabled: bool,
+    begin: String,
+    end: String,
+    days: [bool; 7],
+}
+
+// Enabled, begin, end and then the days
+const FIELDS_COUNT: usize = 3 + DAYS.len();
+
+impl ScheduleForm {
+    fn new(schedule: &AltSpeedSchedule) -> Self {
+        Self {
+            enabled: schedule.alt_speed_time_enabled,
+            begin: minutes_to_time(schedule.alt_speed_time_begin),
+            end: minutes_to_time(schedule.alt_speed_time_end),
+            days: DAYS.map(|(_, bit)| schedule.alt_speed_time_day & bit != 0),
+        }
+    }
+
+    fn session_set_args(&self) -> Result<SessionSetArgs, String> {
+        let begin = time_to_minutes(&self.begin)
+            .ok_or_else(|| format!("\"{}\" isn't a valid HH:MM time", self.begin))?;
+        let end = time_to_minutes(&self.end)
+            .ok_or_else(|| format!("\"{}\" isn't a valid HH:MM time", self.end))?;
+        let days = DAYS
+            .iter()
+            .zip(self.days)
+            .filter(|(_, enabled)| *enabled)
+            .fold(0, |days, ((_, bit), _)| days | bit);
+
+        Ok(SessionSetArgs {
+            alt_speed_time_enabled: Some(self.enabled),
+            alt_speed_time_begin: Some(begin),
+            alt_speed_time_end: Some(end),
+            alt_speed_time_day: Some(days),
+            ..Default::default()
+        })
+    }
+}
+
+fn minutes_to_time(minutes: i32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn time_to_minutes(time: &str) -> Option<i32> {
+    let (hours, minutes) = time.split_once(':')?;
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    ((0..24).contains(&hours) && (0..60).contains(&minutes)).then_some(hours * 60 + minutes)
+}
+
+async fn fetch_schedule(ctx: app::Ctx) {
+    let (schedule_tx, schedule_rx) = oneshot::channel();
+    ctx.send_torrent_action(TorrentAction::GetAltSpeedSchedule(schedule_tx));
+
+    match schedule_rx.await.unwrap() {
+        Ok(schedule) => {
+            ctx.send_update_action(UpdateAction::AltSpeedSchedule(Box::new(schedule)));
+        }
+        Err(err_message) => {
+            ctx.send_update_action(UpdateAction::Error(err_message));
+        }
+    }
+}
+
+impl AltSpeedPopup {
+    pub fn new(ctx: app::Ctx) -> Self {
+        let schedule_task_handle = tokio::task::spawn(fetch_schedule(ctx.clone()));
+
+        Self {
+            ctx,
+            schedule: None,
+            selected: 0,
+            error: None,
+            schedule_task_handle,
+        }
+    }
+
+    fn close(&mut self) -> ComponentAction {
+        self.schedule_task_handle.abort();
+        if self.schedule.is_some() {
+            self.ctx
+                .send_update_action(UpdateAction::SwitchToNormalMode);
+        }
+        ComponentAction::Quit
+    }
+
+    fn confirm(&mut self) -> ComponentAction {
+        let Some(schedule) = &self.schedule else {
+            return ComponentAction::Nothing;
+        };
+
+        match schedule.session_set_args() {
+            Ok(args) => {
+                self.ctx
+                    .send_torrent_action(TorrentAction::SetSessionArgs(Box::new(args)));
+                self.close()
+            }
+            Err(msg) => {
+                self.error = Some(msg);
+                self.ctx.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
+        let Some(schedule) = &mut self.schedule else {
+            return ComponentAction::Nothing;
+        };
+
+        match input.code {
+            KeyCode::Esc => return self.close(),
+            KeyCode::Enter => return self.confirm(),
+            KeyCode::Tab | KeyCode::Down => {
+                self.selected = (self.selected + 1) % FIELDS_COUNT;
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(FIELDS_COUNT - 1);
+            }
+            code => match (self.selected, code) {
+                (0, KeyCode::Char(' ')) => schedule.enabled = !schedule.enabled,
+                (1 | 2, KeyCode::Char(c)) if c.is_ascii_digit() || c == ':' => {
+                    let time = if self.selected == 1 {
+                        &mut schedule.begin
+                    } else {
+                        &mut schedule.end
+                    };
+                    time.push(c);
+                }
+                (1 | 2, KeyCode::Backspace) => {
+                    let time = if self.selected == 1 {
+                        &mut schedule.begin
+                    } else {
+                        &mut schedule.end
+                    };
+                    time.pop();
+                }
+                (day, KeyCode::Char(' ')) if day >= 3 => {
+                    schedule.days[day - 3] = !schedule.days[day - 3];
+                }
+                _ => return ComponentAction::Nothing,
+            },
+        }
+
+        self.error = None;
+        self.ctx.send_action(Action::Render);
+        ComponentAction::Nothing
+    }
+
+    fn paste(&mut self, text: &str) {
+        let Some(schedule) = &mut self.schedule else {
+            return;
+        };
+
+        let time = match self.selected {
+            1 => &mut schedule.begin,
+            2 => &mut schedule.end,
+            _ => return,
+        };
+
+        let pasted: String = text
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == ':')
+            .collect();
+        if pasted.is_empty() {
+            return;
+        }
+        time.push_str(&pasted);
+
+        self.error = None;
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn form_lines(&self, schedule: &ScheduleForm) -> Vec<Line<'static>> {
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+        let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" }.to_string();
+
+        let mut fields = vec![
+            ("Slow down on schedule", checkbox(schedule.enabled)),
+            ("From (HH:MM)", schedule.begin.clone()),
+            ("To (HH:MM)", schedule.end.clone()),
+        ];
+        fields.extend(
+            DAYS.iter()
+                .zip(schedule.days)
+                .map(|((name, _), enabled)| (*name, checkbox(enabled))),
+        );
+
+        let mut lines = vec![];
+        for (idx, (name, value)) in fields.into_iter().enumerate() {
+            if idx == 3 {
+                lines.push(Line::default());
+                lines.push(Line::styled("Days", accent_style.bold()));
+            }
+
+            let mut line = Line::from(format!("  {name}: {value}"));
+            if idx == self.selected {
+                line = line.style(CONFIG.theme.selected_row_style());
+            }
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    fn keybinding_tip() -> Line<'static> {
+        if !CONFIG.general.beginner_mode {
+            return Line::default();
+        }
+
+        let key_style = Style::new().fg(CONFIG.general.accent_color).underlined();
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled("Tab", key_style),
+            Span::raw(" - next field | "),
+            Span::styled("Space", key_style),
+            Span::raw(" - toggle | "),
+            Span::styled("Enter", key_style),
+            Span::raw(" - save "),
+        ])
+    }
+}
+
+impl Component for AltSpeedPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                self.paste(&text);
+                ComponentAction::Nothing
+            }
+            _ if action.is_soft_quit() => self.close(),
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn handle_update_action(&mut self, action: UpdateAction) {
+        if let UpdateAction::AltSpeedSchedule(schedule) = action {
+            if self.schedule.is_none() {
+                self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+            }
+            self.schedule = Some(ScheduleForm::new(&schedule));
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 50, 75);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+        let text_rect = block_rect.inner(Margin::new(3, 2));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(
+                " Alternative speed schedule ".set_style(title_style),
+            ))
+            .title(
+                Title::from(" [ SAVE ] ".set_style(title_style.bold()))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            )
+            .title(
+                Title::from(Self::keybinding_tip())
+                    .alignment(Alignment::Left)
+                    .position(Position::Bottom),
+            );
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+
+        let Some(schedule) = &self.schedule else {
+            f.render_widget(Paragraph::new("Loading..."), text_rect);
+            return;
+        };
+
+        let [form_rect, error_rect] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(text_rect);
+
+        f.render_widget(Paragraph::new(self.form_lines(schedule)), form_rect);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str()).fg(CONFIG.theme.error);
+            f.render_widget(error, error_rect);
+        }
+    }
+}
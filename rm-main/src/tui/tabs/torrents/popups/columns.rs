@@ -0,0 +1,133 @@
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, List, ListItem, ListState,
+    },
+};
+use rm_config::CONFIG;
+
+use crate::tui::{
+    app,
+    components::{Component, ComponentAction},
+    main_window::centered_rect,
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    header::Header,
+};
+
+pub struct ColumnsPopup {
+    ctx: app::Ctx,
+    // Shown columns first, in their current order, then all the hidden ones
+    columns: Vec<(Header, bool)>,
+    state: ListState,
+}
+
+impl ColumnsPopup {
+    pub fn new(ctx: app::Ctx, shown: &[Header]) -> Self {
+        let mut columns: Vec<(Header, bool)> = shown.iter().map(|header| (*header, true)).collect();
+        columns.extend(
+            Header::ALL
+                .into_iter()
+                .filter(|header| !shown.contains(header))
+                .map(|header| (header, false)),
+        );
+
+        Self {
+            ctx,
+            columns,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn select_offset(&mut self, forward: bool) {
+        let len = self.columns.len();
+        let selected = self.state.selected().unwrap_or_default();
+        let next = if forward {
+            (selected + 1) % len
+        } else {
+            (selected + len - 1) % len
+        };
+        self.state.select(Some(next));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+
+        let shown_count = self.columns.iter().filter(|(_, shown)| *shown).count();
+        let (_, shown) = &mut self.columns[selected];
+        // Keep at least one column so the table doesn't disappear
+        if *shown && shown_count == 1 {
+            return;
+        }
+        *shown = !*shown;
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn apply(&self) {
+        let columns = self
+            .columns
+            .iter()
+            .filter(|(_, shown)| *shown)
+            .map(|(header, _)| *header)
+            .collect();
+        self.ctx
+            .send_update_action(UpdateAction::SetColumns(columns));
+    }
+}
+
+impl Component for ColumnsPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+        match action {
+            A::Up => self.select_offset(false),
+            A::Down => self.select_offset(true),
+            A::Select => self.toggle_selected(),
+            A::Confirm => {
+                self.apply();
+                return ComponentAction::Quit;
+            }
+            _ if action.is_soft_quit() => return ComponentAction::Quit,
+            _ => (),
+        }
+        ComponentAction::Nothing
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 40, 75);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let mut block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(" Columns ".set_style(title_style)));
+        if CONFIG.general.beginner_mode {
+            block = block.title(
+                Title::from(" Space - toggle | Enter - apply ")
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            );
+        }
+
+        let items: Vec<ListItem> = self
+            .columns
+            .iter()
+            .map(|(header, shown)| {
+                let checkbox = if *shown { "[x]" } else { "[ ]" };
+                ListItem::new(format!(" {checkbox} {}", header.description()))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(CONFIG.theme.selected_row_style());
+
+        f.render_widget(Clear, popup_rect);
+        f.render_stateful_widget(list, block_rect, &mut self.state);
+    }
+}
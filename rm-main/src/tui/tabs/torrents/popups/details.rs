@@ -0,0 +1,528 @@
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap,
+    },
+};
+use rm_config::CONFIG;
+use tokio::{sync::oneshot, task::JoinHandle};
+use transmission_rpc::types::{ErrorType, Id, Torrent};
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction, InputManager},
+        main_window::centered_rect,
+    },
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    session::{EffectiveLimits, LimitSource},
+};
+
+pub struct DetailsPopup {
+    ctx: app::Ctx,
+    torrent: Option<Torrent>,
+    limits: Option<EffectiveLimits>,
+    torrent_id: Id,
+    focus: DetailsFocus,
+    trackers_state: ListState,
+    labels_state: ListState,
+    input: Option<InputManager>,
+    torrent_info_task_handle: JoinHandle<()>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DetailsFocus {
+    Trackers,
+    Labels,
+}
+
+async fn fetch_details(ctx: app::Ctx, torrent_id: Id) {
+    loop {
+        let (torrent_tx, torrent_rx) = oneshot::channel();
+        ctx.send_torrent_action(TorrentAction::GetTorrentsById(
+            vec![torrent_id.clone()],
+            torrent_tx,
+        ));
+
+        match torrent_rx.await.unwrap() {
+            Ok(mut torrents) => {
+                if let Some(torrent) = torrents.pop() {
+                    ctx.send_update_action(UpdateAction::UpdateCurrentTorrent(Box::new(torrent)));
+                }
+            }
+            Err(err_message) => {
+                ctx.send_update_action(UpdateAction::Error(err_message));
+            }
+        };
+
+        let (limits_tx, limits_rx) = oneshot::channel();
+        ctx.send_torrent_action(TorrentAction::GetEffectiveLimits(
+            torrent_id.clone(),
+            limits_tx,
+        ));
+
+        // The torrent may be gone by now, leaving nothing to report
+        match limits_rx.await {
+            Ok(Ok(limits)) => ctx.send_update_action(UpdateAction::EffectiveLimits(limits)),
+            Ok(Err(err_message)) => ctx.send_update_action(UpdateAction::Error(err_message)),
+            Err(_) => (),
+        }
+
+        tokio::time::sleep(Duration::from_secs(6)).await;
+    }
+}
+
+impl DetailsPopup {
+    // `cached` is shown until the fresh details arrive
+    pub fn new(ctx: app::Ctx, torrent_id: Id, cached: Option<Torrent>) -> Self {
+        let torrent_info_task_handle =
+            tokio::task::spawn(fetch_details(ctx.clone(), torrent_id.clone()));
+
+        Self {
+            ctx,
+            torrent: cached,
+            limits: None,
+            torrent_id,
+            focus: DetailsFocus::Trackers,
+            trackers_state: ListState::default().with_selected(Some(0)),
+            labels_state: ListState::default().with_selected(Some(0)),
+            input: None,
+            torrent_info_task_handle,
+        }
+    }
+
+    fn close(&mut self) -> ComponentAction {
+        self.torrent_info_task_handle.abort();
+        ComponentAction::Quit
+    }
+
+    fn refetch(&mut self) {
+        self.torrent_info_task_handle.abort();
+        self.torrent_info_task_handle =
+            tokio::task::spawn(fetch_details(self.ctx.clone(), self.torrent_id.clone()));
+    }
+
+    fn trackers(&self) -> Vec<String> {
+        self.torrent
+            .as_ref()
+            .and_then(|torrent| torrent.trackers.as_ref())
+            .map(|trackers| {
+                trackers
+                    .iter()
+                    .map(|tracker| tracker.announce.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn tracker_id(&self, idx: usize) -> Option<i32> {
+        self.torrent
+            .as_ref()
+            .and_then(|torrent| torrent.trackers.as_ref())
+            .and_then(|trackers| trackers.get(idx))
+            .map(|tracker| tracker.id)
+    }
+
+    fn edit_trackers(&mut self, add: Vec<String>, remove: Vec<i32>) {
+        self.ctx.send_torrent_action(TorrentAction::EditTrackers {
+            id: self.torrent_id.clone(),
+            add,
+            remove,
+        });
+        self.refetch();
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.torrent
+            .as_ref()
+            .and_then(|torrent| torrent.labels.clone())
+            .unwrap_or_default()
+    }
+
+    fn set_labels(&mut self, labels: Vec<String>) {
+        self.ctx.send_torrent_action(TorrentAction::SetLabels {
+            id: self.torrent_id.clone(),
+            labels,
+        });
+        self.refetch();
+    }
+
+    fn focused_list(&mut self) -> (usize, &mut ListState) {
+        match self.focus {
+            DetailsFocus::Trackers => (self.trackers().len(), &mut self.trackers_state),
+            DetailsFocus::Labels => (self.labels().len(), &mut self.labels_state),
+        }
+    }
+
+    fn switch_focus(&mut self) {
+        self.focus = match self.focus {
+            DetailsFocus::Trackers => DetailsFocus::Labels,
+            DetailsFocus::Labels => DetailsFocus::Trackers,
+        };
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn next_item(&mut self) {
+        let (items_count, state) = self.focused_list();
+        if items_count == 0 {
+            return;
+        }
+        let selected = state.selected().unwrap_or_default();
+        state.select(Some((selected + 1) % items_count));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn previous_item(&mut self) {
+        let (items_count, state) = self.focused_list();
+        if items_count == 0 {
+            return;
+        }
+        let selected = state.selected().unwrap_or_default();
+        let previous = if selected == 0 {
+            items_count - 1
+        } else {
+            selected - 1
+        };
+        state.select(Some(previous));
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn remove_selected_item(&mut self) {
+        let items = match self.focus {
+            DetailsFocus::Trackers => self.trackers(),
+            DetailsFocus::Labels => self.labels(),
+        };
+        let (_, state) = self.focused_list();
+        let Some(selected) = state.selected() else {
+            return;
+        };
+        let Some(item) = items.get(selected).cloned() else {
+            return;
+        };
+
+        if selected > 0 && selected + 1 >= items.len() {
+            state.select(Some(selected - 1));
+        }
+
+        match self.focus {
+            DetailsFocus::Trackers => {
+                if let Some(tracker_id) = self.tracker_id(selected) {
+                    self.edit_trackers(vec![], vec![tracker_id]);
+                }
+            }
+            DetailsFocus::Labels => {
+                let labels = items.into_iter().filter(|label| *label != item).collect();
+                self.set_labels(labels);
+            }
+        }
+    }
+
+    fn start_adding_item(&mut self) {
+        let prompt = match self.focus {
+            DetailsFocus::Trackers => "New tracker URL: ",
+            DetailsFocus::Labels => "New label: ",
+        };
+        self.input = Some(InputManager::new(prompt.to_string()));
+        self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
+    fn add_item(&mut self, item: String) {
+        match self.focus {
+            DetailsFocus::Trackers => self.edit_trackers(vec![item], vec![]),
+            DetailsFocus::Labels => {
+                let mut labels = self.labels();
+                if !labels.contains(&item) {
+                    labels.push(item);
+                    self.set_labels(labels);
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) {
+        let Some(input_manager) = &mut self.input else {
+            return;
+        };
+
+        match input.code {
+            KeyCode::Enter => {
+                let item = input_manager.text().trim().to_string();
+                self.input = None;
+                self.ctx
+                    .send_update_action(UpdateAction::SwitchToNormalMode);
+                if !item.is_empty() {
+                    self.add_item(item);
+                }
+            }
+            KeyCode::Esc => {
+                self.input = None;
+                self.ctx
+                    .send_update_action(UpdateAction::SwitchToNormalMode);
+            }
+            _ => {
+                if input_manager.handle_key(input).is_some() {
+                    self.ctx.send_action(Action::Render);
+                }
+            }
+        }
+    }
+
+    fn keybinding_tip(&self) -> Line<'static> {
+        if !CONFIG.general.beginner_mode {
+            return Line::default();
+        }
+
+        let mut keys = vec![];
+        let key_style = Style::new().fg(CONFIG.general.accent_color).underlined();
+        let item = match self.focus {
+            DetailsFocus::Trackers => "tracker",
+            DetailsFocus::Labels => "label",
+        };
+
+        if let Some(key) = CONFIG.keybindings.get_keys_for_action(Action::AddMagnet) {
+            keys.push(Span::raw(" "));
+            keys.push(Span::styled(key, key_style));
+            keys.push(Span::raw(format!(" - add {item} | ")));
+        }
+
+        if let Some(key) = CONFIG
+            .keybindings
+            .get_keys_for_action(Action::DeleteWithoutFiles)
+        {
+            keys.push(Span::styled(key, key_style));
+            keys.push(Span::raw(format!(" - remove {item} | ")));
+        }
+
+        if let Some(key) = CONFIG.keybindings.get_keys_for_action(Action::ChangeFocus) {
+            keys.push(Span::styled(key, key_style));
+            keys.push(Span::raw(" - switch list "));
+        }
+
+        Line::from(keys)
+    }
+
+    fn detail_line<'a>(name: &'a str, value: impl Into<String>) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(name, Style::default().bold()),
+            Span::raw(": "),
+            Span::raw(value.into()),
+        ])
+    }
+
+    fn details_lines(torrent: &Torrent, limits: Option<EffectiveLimits>) -> Vec<Line<'_>> {
+        let units = CONFIG.general.units;
+        let mut lines = vec![];
+
+        if let Some(name) = &torrent.name {
+            lines.push(Self::detail_line("Name", name));
+        }
+
+        if let Some(download_dir) = &torrent.download_dir {
+            lines.push(Self::detail_line("Directory", download_dir));
+        }
+
+        if let Some(size) = torrent.size_when_done {
+            lines.push(Self::detail_line("Size", units.format_size(size)));
+        }
+
+        if let Some(percent_done) = torrent.percent_done {
+            lines.push(Self::detail_line(
+                "Progress",
+                format!("{:.2}%", percent_done * 100f32),
+            ));
+        }
+
+        if let Some(ratio) = torrent.upload_ratio {
+            lines.push(Self::detail_line("Ratio", format!("{ratio:.2}")));
+        }
+
+        if let Some(added_date) = torrent.added_date {
+            lines.push(Self::detail_line("Added", absolute_date(added_date)));
+        }
+
+        let done_date = match torrent.done_date {
+            Some(done_date) if done_date != 0 => absolute_date(done_date),
+            _ => "-".to_string(),
+        };
+        lines.push(Self::detail_line("Completed", done_date));
+
+        if let Some(limits) = limits {
+            lines.push(Self::detail_line(
+                "Download limit",
+                limit_format(limits.download),
+            ));
+            lines.push(Self::detail_line(
+                "Upload limit",
+                limit_format(limits.upload),
+            ));
+        }
+
+        if let (Some(error), Some(error_string)) = (&torrent.error, &torrent.error_string) {
+            if *error != ErrorType::Ok {
+                lines.push(Self::detail_line("Error", error_string).fg(CONFIG.theme.error));
+            }
+        }
+
+        lines
+    }
+}
+
+fn limit_format(limit: Option<(i32, LimitSource)>) -> String {
+    match limit {
+        // Transmission counts KB/s in thousands of bytes
+        Some((kbps, source)) => format!(
+            "{} ({})",
+            CONFIG.general.units.format_speed(i64::from(kbps) * 1000),
+            source.name()
+        ),
+        None => "unlimited".to_string(),
+    }
+}
+
+fn absolute_date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|date| {
+            date.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl Component for DetailsPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+        match action {
+            A::Input(input) => {
+                self.handle_input(input);
+                ComponentAction::Nothing
+            }
+            A::Paste(text) => {
+                if let Some(input_manager) = &mut self.input {
+                    if input_manager.paste(&text) {
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                ComponentAction::Nothing
+            }
+            _ if action.is_soft_quit() => self.close(),
+            A::Confirm => self.close(),
+            A::Up => {
+                self.previous_item();
+                ComponentAction::Nothing
+            }
+            A::Down => {
+                self.next_item();
+                ComponentAction::Nothing
+            }
+            A::ChangeFocus => {
+                self.switch_focus();
+                ComponentAction::Nothing
+            }
+            A::AddMagnet => {
+                self.start_adding_item();
+                ComponentAction::Nothing
+            }
+            A::DeleteWithoutFiles | A::DeleteWithFiles => {
+                self.remove_selected_item();
+                ComponentAction::Nothing
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn handle_update_action(&mut self, action: UpdateAction) {
+        match action {
+            UpdateAction::UpdateCurrentTorrent(torrent) => self.torrent = Some(*torrent),
+            UpdateAction::EffectiveLimits(limits) => self.limits = Some(limits),
+            _ => (),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 75, 75);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+        let text_rect = block_rect.inner(Margin::new(3, 2));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(" Details ".set_style(title_style)))
+            .title(
+                Title::from(" [ CLOSE ] ".set_style(title_style.bold()))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            )
+            .title(
+                Title::from(self.keybinding_tip())
+                    .alignment(Alignment::Left)
+                    .position(Position::Bottom),
+            );
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+
+        let Some(torrent) = &self.torrent else {
+            f.render_widget(Paragraph::new("Loading..."), text_rect);
+            return;
+        };
+
+        let details_lines = Self::details_lines(torrent, self.limits);
+        let details_height = u16::try_from(details_lines.len()).unwrap_or(u16::MAX);
+
+        let [details_rect, lists_rect, input_rect] = Layout::vertical([
+            Constraint::Length(details_height + 1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(text_rect);
+        let [trackers_rect, labels_rect] =
+            Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)])
+                .areas(lists_rect);
+
+        let paragraph = Paragraph::new(details_lines).wrap(Wrap { trim: false });
+        f.render_widget(paragraph, details_rect);
+
+        let list = |items: Vec<String>, title: &'static str, focused: bool| {
+            let highlight_style = if focused {
+                CONFIG.theme.selected_row_style()
+            } else {
+                Style::default()
+            };
+            List::new(items.into_iter().map(ListItem::new))
+                .block(
+                    Block::new()
+                        .borders(Borders::TOP)
+                        .title(title)
+                        .title_style(title_style),
+                )
+                .highlight_style(highlight_style)
+        };
+
+        let trackers_list = list(
+            self.trackers(),
+            " Trackers ",
+            self.focus == DetailsFocus::Trackers,
+        );
+        f.render_stateful_widget(trackers_list, trackers_rect, &mut self.trackers_state);
+
+        let labels_list = list(
+            self.labels(),
+            " Labels ",
+            self.focus == DetailsFocus::Labels,
+        );
+        f.render_stateful_widget(labels_list, labels_rect, &mut self.labels_state);
+
+        if let Some(input) = &mut self.input {
+            input.render(f, input_rect);
+        }
+    }
+}
@@ -67,10 +67,13 @@ enum CurrentFocus {
 }
 
 impl FilesPopup {
-    pub fn new(ctx: app::Ctx, torrent_id: Id) -> Self {
-        let torrent = None;
+    // `cached` is shown until the fresh files arrive
+    pub fn new(ctx: app::Ctx, torrent_id: Id, cached: Option<Torrent>) -> Self {
         let tree_state = TreeState::default();
-        let tree = Node::new();
+        let tree = cached
+            .as_ref()
+            .map_or_else(Node::new, Node::new_from_torrent);
+        let torrent = cached;
 
         let torrent_info_task_handle =
             tokio::task::spawn(fetch_new_files(ctx.clone(), torrent_id.clone()));
@@ -104,7 +107,6 @@ impl FilesPopup {
 }
 
 impl Component for FilesPopup {
-    #[must_use]
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         use Action as A;
         match (action, self.current_focus) {
@@ -121,15 +123,8 @@ impl Component for FilesPopup {
                 return ComponentAction::Quit;
             }
             (A::Select | A::Confirm, CurrentFocus::Files) => {
-                if self.torrent.is_some() {
-                    let mut wanted_ids = self
-                        .torrent
-                        .as_ref()
-                        .unwrap()
-                        .wanted
-                        .as_ref()
-                        .unwrap()
-                        .clone();
+                if let Some(torrent) = &self.torrent {
+                    let mut wanted_ids = torrent.wanted.as_ref().unwrap().clone();
 
                     let selected_ids = self.selected_ids();
 
@@ -249,7 +244,7 @@ impl Component for FilesPopup {
         let info_text_rect = block_rect.inner(Margin::new(3, 2));
 
         let highlight_style = Style::default().fg(CONFIG.general.accent_color);
-        let bold_highlight_style = highlight_style.on_black().bold();
+        let bold_highlight_style = CONFIG.theme.selected_row_style();
 
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
@@ -423,7 +418,7 @@ impl Node {
         transmission_files
     }
 
-    fn make_tree(&self) -> Vec<TreeItem<String>> {
+    fn make_tree(&self) -> Vec<TreeItem<'_, String>> {
         let mut tree_items = vec![];
         for transmission_file in &self.items {
             let name = {
@@ -3,13 +3,24 @@ use crate::tui::{
     components::{Component, ComponentAction},
 };
 
-use self::{files::FilesPopup, stats::StatisticsPopup};
+use self::{
+    alt_speed::AltSpeedPopup, columns::ColumnsPopup, details::DetailsPopup, files::FilesPopup,
+    session_info::SessionInfoPopup, session_settings::SessionSettingsPopup, stats::StatisticsPopup,
+    trackers::TrackersPopup,
+};
+use super::rustmission_torrent::RustmissionTorrent;
 use rm_shared::action::{Action, UpdateAction};
 
 use ratatui::prelude::*;
 
+pub mod alt_speed;
+pub mod columns;
+pub mod details;
 pub mod files;
+pub mod session_info;
+pub mod session_settings;
 pub mod stats;
+pub mod trackers;
 
 pub struct PopupManager {
     ctx: app::Ctx,
@@ -18,7 +29,13 @@ pub struct PopupManager {
 
 pub enum CurrentPopup {
     Stats(StatisticsPopup),
-    Files(FilesPopup),
+    Files(Box<FilesPopup>),
+    Details(Box<DetailsPopup>),
+    SessionSettings(SessionSettingsPopup),
+    SessionInfo(SessionInfoPopup),
+    Columns(ColumnsPopup),
+    Trackers(TrackersPopup),
+    AltSpeed(AltSpeedPopup),
 }
 
 impl PopupManager {
@@ -40,6 +57,19 @@ impl PopupManager {
     pub fn close_popup(&mut self) {
         self.current_popup = None;
     }
+
+    pub const fn is_showing_trackers(&self) -> bool {
+        matches!(self.current_popup, Some(CurrentPopup::Trackers(_)))
+    }
+
+    // `with_trackers` tells whether the torrents were fetched along with their trackers
+    pub fn update_torrents(&mut self, torrents: &[RustmissionTorrent], with_trackers: bool) {
+        if let Some(CurrentPopup::Trackers(popup)) = &mut self.current_popup {
+            if with_trackers {
+                popup.update_groups(torrents);
+            }
+        }
+    }
 }
 
 impl Component for PopupManager {
@@ -58,14 +88,55 @@ impl Component for PopupManager {
                         self.ctx.send_action(Action::Render);
                     }
                 }
+                CurrentPopup::Details(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                CurrentPopup::SessionSettings(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                CurrentPopup::SessionInfo(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                CurrentPopup::Columns(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                CurrentPopup::Trackers(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
+                CurrentPopup::AltSpeed(popup) => {
+                    if popup.handle_actions(action).is_quit() {
+                        self.close_popup();
+                        self.ctx.send_action(Action::Render);
+                    }
+                }
             }
         }
         ComponentAction::Nothing
     }
 
     fn handle_update_action(&mut self, action: UpdateAction) {
-        if let Some(CurrentPopup::Files(popup)) = &mut self.current_popup {
-            popup.handle_update_action(action);
+        match &mut self.current_popup {
+            Some(CurrentPopup::Files(popup)) => popup.handle_update_action(action),
+            Some(CurrentPopup::Details(popup)) => popup.handle_update_action(action),
+            Some(CurrentPopup::SessionSettings(popup)) => popup.handle_update_action(action),
+            Some(CurrentPopup::SessionInfo(popup)) => popup.handle_update_action(action),
+            Some(CurrentPopup::AltSpeed(popup)) => popup.handle_update_action(action),
+            _ => (),
         }
     }
 
@@ -78,6 +149,24 @@ impl Component for PopupManager {
                 CurrentPopup::Files(popup) => {
                     popup.render(f, rect);
                 }
+                CurrentPopup::Details(popup) => {
+                    popup.render(f, rect);
+                }
+                CurrentPopup::SessionSettings(popup) => {
+                    popup.render(f, rect);
+                }
+                CurrentPopup::SessionInfo(popup) => {
+                    popup.render(f, rect);
+                }
+                CurrentPopup::Columns(popup) => {
+                    popup.render(f, rect);
+                }
+                CurrentPopup::Trackers(popup) => {
+                    popup.render(f, rect);
+                }
+                CurrentPopup::AltSpeed(popup) => {
+                    popup.render(f, rect);
+                }
             }
         }
     }
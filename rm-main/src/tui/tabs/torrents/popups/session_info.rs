@@ -0,0 +1,127 @@
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, Paragraph,
+    },
+};
+use rm_config::CONFIG;
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction},
+        main_window::centered_rect,
+    },
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    session::SessionInfo,
+};
+
+pub struct SessionInfoPopup {
+    ctx: app::Ctx,
+    info: Option<SessionInfo>,
+    info_task_handle: JoinHandle<()>,
+}
+
+async fn fetch_info(ctx: app::Ctx) {
+    let (info_tx, info_rx) = oneshot::channel();
+    ctx.send_torrent_action(TorrentAction::GetSessionInfo(info_tx));
+
+    match info_rx.await.unwrap() {
+        Ok(info) => {
+            ctx.send_update_action(UpdateAction::SessionInfo(Box::new(info)));
+        }
+        Err(err_message) => {
+            ctx.send_update_action(UpdateAction::Error(err_message));
+        }
+    }
+}
+
+fn or_na(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| value.to_string())
+}
+
+fn enabled_str(enabled: Option<bool>) -> String {
+    or_na(enabled.map(|enabled| if enabled { "enabled" } else { "disabled" }))
+}
+
+impl SessionInfoPopup {
+    pub fn new(ctx: app::Ctx) -> Self {
+        let info_task_handle = tokio::task::spawn(fetch_info(ctx.clone()));
+
+        Self {
+            ctx,
+            info: None,
+            info_task_handle,
+        }
+    }
+
+    fn info_lines(info: &SessionInfo) -> Vec<Line<'static>> {
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+        let line = |name: &'static str, value: String| {
+            Line::from(vec![Span::styled(name, accent_style), Span::raw(value)])
+        };
+
+        vec![
+            line("Transmission version: ", or_na(info.version.as_ref())),
+            line("RPC version: ", or_na(info.rpc_version)),
+            line("Config directory: ", or_na(info.config_dir.as_ref())),
+            line("DHT: ", enabled_str(info.dht_enabled)),
+            line("PEX: ", enabled_str(info.pex_enabled)),
+            line(
+                "Port forwarding: ",
+                enabled_str(info.port_forwarding_enabled),
+            ),
+        ]
+    }
+}
+
+impl Component for SessionInfoPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+        match action {
+            _ if action.is_soft_quit() || action == A::Confirm => {
+                self.info_task_handle.abort();
+                ComponentAction::Quit
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn handle_update_action(&mut self, action: UpdateAction) {
+        if let UpdateAction::SessionInfo(info) = action {
+            self.info = Some(*info);
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 50, 50);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+        let text_rect = block_rect.inner(Margin::new(3, 2));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(" Server info ".set_style(title_style)))
+            .title(
+                Title::from(" [ CLOSE ] ".set_style(title_style.bold()))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            );
+
+        let paragraph = match &self.info {
+            Some(info) => Paragraph::new(Self::info_lines(info)),
+            None => Paragraph::new("Loading..."),
+        };
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+        f.render_widget(paragraph, text_rect);
+    }
+}
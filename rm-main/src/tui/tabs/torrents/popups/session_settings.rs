@@ -0,0 +1,415 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, Paragraph,
+    },
+};
+use rm_config::CONFIG;
+use tokio::{sync::oneshot, task::JoinHandle};
+use transmission_rpc::types::SessionSetArgs;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction},
+        main_window::centered_rect,
+    },
+};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    session::SessionSettings,
+};
+
+pub struct SessionSettingsPopup {
+    ctx: app::Ctx,
+    fields: Option<Vec<SettingField>>,
+    selected: usize,
+    error: Option<String>,
+    saved: bool,
+    settings_task_handle: JoinHandle<()>,
+}
+
+#[derive(Clone, Copy)]
+enum FieldKind {
+    DownloadLimited,
+    DownloadLimit,
+    UploadLimited,
+    UploadLimit,
+    PeerLimitGlobal,
+    PeerLimitPerTorrent,
+    DownloadQueueSize,
+    SeedQueueSize,
+    Pex,
+    Dht,
+    Lpd,
+}
+
+impl FieldKind {
+    const fn group(self) -> &'static str {
+        match self {
+            Self::DownloadLimited
+            | Self::DownloadLimit
+            | Self::UploadLimited
+            | Self::UploadLimit => "Speed limits",
+            Self::PeerLimitGlobal | Self::PeerLimitPerTorrent => "Peers",
+            Self::DownloadQueueSize | Self::SeedQueueSize => "Queue",
+            Self::Pex | Self::Dht | Self::Lpd => "Network",
+        }
+    }
+
+    const fn is_queue_size(self) -> bool {
+        matches!(self, Self::DownloadQueueSize | Self::SeedQueueSize)
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::DownloadLimited => "Limit download speed",
+            Self::DownloadLimit => "Download limit (KB/s)",
+            Self::UploadLimited => "Limit upload speed",
+            Self::UploadLimit => "Upload limit (KB/s)",
+            Self::PeerLimitGlobal => "Max peers overall",
+            Self::PeerLimitPerTorrent => "Max peers per torrent",
+            Self::DownloadQueueSize => "Downloading at once",
+            Self::SeedQueueSize => "Seeding at once",
+            Self::Pex => "Peer exchange (PEX)",
+            Self::Dht => "Distributed hash table (DHT)",
+            Self::Lpd => "Local peer discovery (LPD)",
+        }
+    }
+}
+
+enum FieldValue {
+    Toggle(bool),
+    Number(String),
+}
+
+struct SettingField {
+    kind: FieldKind,
+    value: FieldValue,
+}
+
+impl SettingField {
+    const fn toggle(kind: FieldKind, value: bool) -> Self {
+        Self {
+            kind,
+            value: FieldValue::Toggle(value),
+        }
+    }
+
+    fn number(kind: FieldKind, value: i32) -> Self {
+        Self {
+            kind,
+            value: FieldValue::Number(value.to_string()),
+        }
+    }
+
+    // None when the field isn't a toggle
+    const fn enabled(&self) -> Option<bool> {
+        match self.value {
+            FieldValue::Toggle(enabled) => Some(enabled),
+            FieldValue::Number(_) => None,
+        }
+    }
+
+    // None when the field isn't a number, an error when its text isn't a valid one
+    fn parsed_number(&self) -> Result<Option<i32>, String> {
+        let FieldValue::Number(text) = &self.value else {
+            return Ok(None);
+        };
+        let Ok(number) = text.parse::<i32>() else {
+            return Err(format!("\"{}\" has to be a number", self.kind.name()));
+        };
+        if number < 1 && self.kind.is_queue_size() {
+            return Err(format!("\"{}\" has to be at least 1", self.kind.name()));
+        }
+        Ok(Some(number))
+    }
+}
+
+fn fields_from_settings(settings: &SessionSettings) -> Vec<SettingField> {
+    use FieldKind as F;
+    vec![
+        SettingField::toggle(F::DownloadLimited, settings.speed_limit_down_enabled),
+        SettingField::number(F::DownloadLimit, settings.speed_limit_down),
+        SettingField::toggle(F::UploadLimited, settings.speed_limit_up_enabled),
+        SettingField::number(F::UploadLimit, settings.speed_limit_up),
+        SettingField::number(F::PeerLimitGlobal, settings.peer_limit_global),
+        SettingField::number(F::PeerLimitPerTorrent, settings.peer_limit_per_torrent),
+        SettingField::number(F::DownloadQueueSize, settings.download_queue_size),
+        SettingField::number(F::SeedQueueSize, settings.seed_queue_size),
+        SettingField::toggle(F::Pex, settings.pex_enabled),
+        SettingField::toggle(F::Dht, settings.dht_enabled),
+        SettingField::toggle(F::Lpd, settings.lpd_enabled),
+    ]
+}
+
+async fn fetch_settings(ctx: app::Ctx) {
+    let (settings_tx, settings_rx) = oneshot::channel();
+    ctx.send_torrent_action(TorrentAction::GetSessionSettings(settings_tx));
+
+    match settings_rx.await.unwrap() {
+        Ok(settings) => {
+            ctx.send_update_action(UpdateAction::SessionSettings(Box::new(settings)));
+        }
+        Err(err_message) => {
+            ctx.send_update_action(UpdateAction::Error(err_message));
+        }
+    }
+}
+
+impl SessionSettingsPopup {
+    pub fn new(ctx: app::Ctx) -> Self {
+        let settings_task_handle = tokio::task::spawn(fetch_settings(ctx.clone()));
+
+        Self {
+            ctx,
+            fields: None,
+            selected: 0,
+            error: None,
+            saved: false,
+            settings_task_handle,
+        }
+    }
+
+    fn close(&mut self) -> ComponentAction {
+        self.settings_task_handle.abort();
+        if self.fields.is_some() {
+            self.ctx
+                .send_update_action(UpdateAction::SwitchToNormalMode);
+        }
+        ComponentAction::Quit
+    }
+
+    fn session_set_args(fields: &[SettingField]) -> Result<SessionSetArgs, String> {
+        let mut args = SessionSetArgs::default();
+
+        for field in fields {
+            match field.kind {
+                FieldKind::DownloadLimited => args.speed_limit_down_enabled = field.enabled(),
+                FieldKind::UploadLimited => args.speed_limit_up_enabled = field.enabled(),
+                FieldKind::Pex => args.pex_enabled = field.enabled(),
+                FieldKind::Dht => args.dht_enabled = field.enabled(),
+                FieldKind::Lpd => args.lpd_enabled = field.enabled(),
+                FieldKind::DownloadLimit => args.speed_limit_down = field.parsed_number()?,
+                FieldKind::UploadLimit => args.speed_limit_up = field.parsed_number()?,
+                FieldKind::PeerLimitGlobal => args.peer_limit_global = field.parsed_number()?,
+                FieldKind::PeerLimitPerTorrent => {
+                    args.peer_limit_per_torrent = field.parsed_number()?
+                }
+                FieldKind::DownloadQueueSize => args.download_queue_size = field.parsed_number()?,
+                FieldKind::SeedQueueSize => args.seed_queue_size = field.parsed_number()?,
+            }
+        }
+
+        Ok(args)
+    }
+
+    fn confirm(&mut self) -> ComponentAction {
+        let Some(fields) = &self.fields else {
+            return ComponentAction::Nothing;
+        };
+
+        match Self::session_set_args(fields) {
+            Ok(args) => {
+                self.ctx
+                    .send_torrent_action(TorrentAction::SetSessionArgs(Box::new(args)));
+                // Read the settings back to show what Transmission actually applied
+                self.settings_task_handle.abort();
+                self.settings_task_handle = tokio::task::spawn(fetch_settings(self.ctx.clone()));
+                self.saved = true;
+                self.ctx.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+            Err(msg) => {
+                self.error = Some(msg);
+                self.ctx.send_action(Action::Render);
+                ComponentAction::Nothing
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
+        let Some(fields) = &mut self.fields else {
+            return ComponentAction::Nothing;
+        };
+
+        match input.code {
+            KeyCode::Esc => return self.close(),
+            KeyCode::Enter => return self.confirm(),
+            KeyCode::Tab | KeyCode::Down => {
+                self.selected = (self.selected + 1) % fields.len();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(fields.len() - 1);
+            }
+            code => {
+                let field = &mut fields[self.selected];
+                match (&mut field.value, code) {
+                    (FieldValue::Toggle(enabled), KeyCode::Char(' ')) => *enabled = !*enabled,
+                    (FieldValue::Number(text), KeyCode::Char(c)) if c.is_ascii_digit() => {
+                        text.push(c);
+                    }
+                    (FieldValue::Number(_), KeyCode::Char(_)) => {
+                        self.error = Some(format!("\"{}\" accepts only digits", field.kind.name()));
+                        self.ctx.send_action(Action::Render);
+                        return ComponentAction::Nothing;
+                    }
+                    (FieldValue::Number(text), KeyCode::Backspace) => {
+                        text.pop();
+                    }
+                    _ => return ComponentAction::Nothing,
+                }
+            }
+        }
+
+        self.error = None;
+        self.saved = false;
+        self.ctx.send_action(Action::Render);
+        ComponentAction::Nothing
+    }
+
+    // Only numbers can be pasted, and only in full
+    fn paste(&mut self, text: &str) {
+        let Some(fields) = &mut self.fields else {
+            return;
+        };
+
+        let field = &mut fields[self.selected];
+        let FieldValue::Number(value) = &mut field.value else {
+            return;
+        };
+
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if text.chars().all(|c| c.is_ascii_digit()) {
+            value.push_str(text);
+            self.error = None;
+            self.saved = false;
+        } else {
+            self.error = Some(format!("\"{}\" accepts only digits", field.kind.name()));
+        }
+        self.ctx.send_action(Action::Render);
+    }
+
+    fn field_lines(&self, fields: &[SettingField]) -> Vec<Line<'static>> {
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+        let mut lines = vec![];
+        let mut current_group = None;
+
+        for (idx, field) in fields.iter().enumerate() {
+            let group = field.kind.group();
+            if current_group != Some(group) {
+                if current_group.is_some() {
+                    lines.push(Line::default());
+                }
+                lines.push(Line::styled(group, accent_style.bold()));
+                current_group = Some(group);
+            }
+
+            let value = match &field.value {
+                FieldValue::Toggle(true) => "[x]".to_string(),
+                FieldValue::Toggle(false) => "[ ]".to_string(),
+                FieldValue::Number(text) => text.clone(),
+            };
+
+            let mut line = Line::from(vec![
+                Span::raw(format!("  {}: ", field.kind.name())),
+                Span::raw(value),
+            ]);
+            if idx == self.selected {
+                line = line.style(CONFIG.theme.selected_row_style());
+            }
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    fn keybinding_tip() -> Line<'static> {
+        if !CONFIG.general.beginner_mode {
+            return Line::default();
+        }
+
+        let key_style = Style::new().fg(CONFIG.general.accent_color).underlined();
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled("Tab", key_style),
+            Span::raw(" - next field | "),
+            Span::styled("Space", key_style),
+            Span::raw(" - toggle | "),
+            Span::styled("Enter", key_style),
+            Span::raw(" - save "),
+        ])
+    }
+}
+
+impl Component for SessionSettingsPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                self.paste(&text);
+                ComponentAction::Nothing
+            }
+            _ if action.is_soft_quit() => self.close(),
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn handle_update_action(&mut self, action: UpdateAction) {
+        if let UpdateAction::SessionSettings(settings) = action {
+            if self.fields.is_none() {
+                self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+            }
+            self.fields = Some(fields_from_settings(&settings));
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 50, 75);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+        let text_rect = block_rect.inner(Margin::new(3, 2));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(" Session settings ".set_style(title_style)))
+            .title(
+                Title::from(" [ SAVE ] ".set_style(title_style.bold()))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            )
+            .title(
+                Title::from(Self::keybinding_tip())
+                    .alignment(Alignment::Left)
+                    .position(Position::Bottom),
+            );
+
+        f.render_widget(Clear, popup_rect);
+        f.render_widget(block, block_rect);
+
+        let Some(fields) = &self.fields else {
+            f.render_widget(Paragraph::new("Loading..."), text_rect);
+            return;
+        };
+
+        let [fields_rect, error_rect] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(text_rect);
+
+        f.render_widget(Paragraph::new(self.field_lines(fields)), fields_rect);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str()).fg(CONFIG.theme.error);
+            f.render_widget(error, error_rect);
+        } else if self.saved {
+            let saved = Paragraph::new("Saved").fg(CONFIG.general.accent_color);
+            f.render_widget(saved, error_rect);
+        }
+    }
+}
@@ -11,7 +11,7 @@ use ratatui::{
 use rm_config::CONFIG;
 use transmission_rpc::types::SessionStats;
 
-use rm_shared::{action::Action, utils::bytes_to_human_format};
+use rm_shared::action::Action;
 
 use crate::tui::{
     components::{Component, ComponentAction},
@@ -55,8 +55,8 @@ impl Component for StatisticsPopup {
 
         let uploaded_bytes = self.stats.cumulative_stats.uploaded_bytes;
         let downloaded_bytes = self.stats.cumulative_stats.downloaded_bytes;
-        let uploaded = bytes_to_human_format(uploaded_bytes);
-        let downloaded = bytes_to_human_format(downloaded_bytes);
+        let uploaded = CONFIG.general.units.format_size(uploaded_bytes);
+        let downloaded = CONFIG.general.units.format_size(downloaded_bytes);
         let ratio = uploaded_bytes as f64 / downloaded_bytes as f64;
         let text = format!("Uploaded: {uploaded}\nDownloaded: {downloaded}\nRatio: {ratio:.2}");
         let paragraph = Paragraph::new(text);
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, Paragraph,
+    },
+};
+use rm_config::CONFIG;
+use rm_shared::{
+    action::Action,
+    utils::{download_speed_format, upload_speed_format},
+};
+use tui_tree_widget::{Tree, TreeItem, TreeState};
+
+use crate::tui::{
+    app,
+    components::{Component, ComponentAction},
+    main_window::centered_rect,
+};
+
+use super::super::rustmission_torrent::RustmissionTorrent;
+
+const NO_TRACKER: &str = "No tracker";
+
+pub struct TrackersPopup {
+    ctx: app::Ctx,
+    // None until torrents fetched along with their trackers come in
+    groups: Option<Vec<TrackerGroup>>,
+    tree_state: TreeState<String>,
+}
+
+struct TrackerGroup {
+    host: String,
+    download_rate: i64,
+    upload_rate: i64,
+    // Name, download rate and upload rate of every torrent in the group
+    torrents: Vec<(String, i64, i64)>,
+}
+
+impl TrackerGroup {
+    fn new(host: String) -> Self {
+        Self {
+            host,
+            download_rate: 0,
+            upload_rate: 0,
+            torrents: vec![],
+        }
+    }
+}
+
+fn speeds(download_rate: i64, upload_rate: i64) -> String {
+    let units = CONFIG.general.units;
+    format!(
+        "{} {}",
+        download_speed_format(&units.format_speed(download_rate)),
+        upload_speed_format(&units.format_speed(upload_rate))
+    )
+}
+
+impl TrackersPopup {
+    pub fn new(ctx: app::Ctx) -> Self {
+        Self {
+            ctx,
+            groups: None,
+            tree_state: TreeState::default(),
+        }
+    }
+
+    // Groups by tracker host, the busiest trackers first.
+    // `torrents` have to be fetched with their trackers.
+    pub fn update_groups(&mut self, torrents: &[RustmissionTorrent]) {
+        let mut groups: HashMap<&str, TrackerGroup> = HashMap::new();
+
+        for torrent in torrents {
+            let host = torrent.tracker_host.as_deref().unwrap_or(NO_TRACKER);
+            let group = groups
+                .entry(host)
+                .or_insert_with(|| TrackerGroup::new(host.to_string()));
+            group.download_rate += torrent.download_rate();
+            group.upload_rate += torrent.upload_rate();
+            group.torrents.push((
+                torrent.torrent_name.clone(),
+                torrent.download_rate(),
+                torrent.upload_rate(),
+            ));
+        }
+
+        let mut groups: Vec<TrackerGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            (b.download_rate + b.upload_rate)
+                .cmp(&(a.download_rate + a.upload_rate))
+                .then_with(|| a.host.cmp(&b.host))
+        });
+        self.groups = Some(groups);
+    }
+
+    fn tree_items(groups: &[TrackerGroup]) -> Vec<TreeItem<'_, String>> {
+        let accent_style = Style::default().fg(CONFIG.general.accent_color);
+
+        groups
+            .iter()
+            .map(|group| {
+                let children = group
+                    .torrents
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (name, download_rate, upload_rate))| {
+                        let text = format!("{name}  {}", speeds(*download_rate, *upload_rate));
+                        TreeItem::new_leaf(idx.to_string(), text)
+                    })
+                    .collect();
+
+                let text = Line::from(vec![
+                    Span::styled(group.host.as_str(), accent_style),
+                    Span::raw(format!(
+                        " ({})  {}",
+                        group.torrents.len(),
+                        speeds(group.download_rate, group.upload_rate)
+                    )),
+                ]);
+                TreeItem::new(group.host.clone(), text, children)
+                    .expect("torrents have unique indexes")
+            })
+            .collect()
+    }
+}
+
+impl Component for TrackersPopup {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        use Action as A;
+        match action {
+            A::Up => {
+                self.tree_state.key_up();
+            }
+            A::Down => {
+                self.tree_state.key_down();
+            }
+            A::Left => {
+                self.tree_state.key_left();
+            }
+            A::Right => {
+                self.tree_state.key_right();
+            }
+            A::Select | A::Confirm => {
+                self.tree_state.toggle_selected();
+            }
+            _ if action.is_soft_quit() || action == A::ShowTrackerGroups => {
+                return ComponentAction::Quit;
+            }
+            _ => return ComponentAction::Nothing,
+        }
+        self.ctx.send_action(Action::Render);
+        ComponentAction::Nothing
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        let popup_rect = centered_rect(rect, 75, 75);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let mut block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(" Trackers ".set_style(title_style)));
+        if CONFIG.general.beginner_mode {
+            block = block.title(
+                Title::from(" Enter - expand/collapse ")
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            );
+        }
+
+        f.render_widget(Clear, popup_rect);
+
+        let groups = match &self.groups {
+            Some(groups) if !groups.is_empty() => groups,
+            groups => {
+                let text = if groups.is_some() {
+                    "No torrents"
+                } else {
+                    "Loading trackers..."
+                };
+                let text_rect = block_rect.inner(Margin::new(3, 2));
+                f.render_widget(block, block_rect);
+                f.render_widget(Paragraph::new(text), text_rect);
+                return;
+            }
+        };
+
+        if self.tree_state.selected().is_empty() {
+            self.tree_state.select_first();
+        }
+
+        let tree_items = Self::tree_items(groups);
+        let tree_widget = Tree::new(&tree_items)
+            .expect("tracker hosts are unique")
+            .block(block)
+            .highlight_style(CONFIG.theme.selected_row_style());
+
+        f.render_stateful_widget(tree_widget, block_rect, &mut self.tree_state);
+    }
+}
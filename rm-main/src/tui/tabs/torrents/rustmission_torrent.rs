@@ -1,16 +1,20 @@
+use std::{cmp::Ordering, collections::HashSet};
+
 use chrono::{Datelike, NaiveDateTime};
 use ratatui::{
     style::{Style, Stylize},
-    text::{Line, Span},
-    widgets::Row,
+    text::{Line, Span, Text},
+    widgets::{Cell, Row},
 };
+use rm_config::CONFIG;
 use rm_shared::{
     header::Header,
     utils::{
-        bytes_to_human_format, download_speed_format, seconds_to_human_format, upload_speed_format,
+        download_speed_format, eta_format, middle_truncated_str, middle_truncation,
+        seconds_to_relative_format, upload_speed_format, NameTruncation,
     },
 };
-use transmission_rpc::types::{ErrorType, Id, Torrent, TorrentStatus};
+use transmission_rpc::types::{ErrorType, Id, Priority, Torrent, TorrentStatus};
 
 #[derive(Clone)]
 pub struct RustmissionTorrent {
@@ -25,93 +29,125 @@ pub struct RustmissionTorrent {
     status: TorrentStatus,
     pub style: Style,
     pub id: Id,
+    pub hash: String,
     pub download_dir: String,
     pub activity_date: NaiveDateTime,
     pub added_date: NaiveDateTime,
+    pub done_date: Option<NaiveDateTime>,
     pub peers_connected: i64,
+    pub peers_sending_to_us: i64,
+    pub peers_getting_from_us: i64,
     pub error: Option<String>,
+    pub labels: Vec<String>,
+    pub bandwidth_priority: Priority,
+    // Host of the first tracker, used for grouping torrents by tracker
+    pub tracker_host: Option<String>,
+    // Uploading paused by limiting it to 0, while downloading goes on
+    pub upload_paused: bool,
+    // Finished downloading a moment ago, highlighted for a while
+    recently_completed: bool,
+    missing: MissingFields,
+    raw: RawValues,
+}
+
+// Fields Transmission didn't send, shown as "?" rather than as made-up values
+#[derive(Clone, Default)]
+struct MissingFields {
+    names: Vec<&'static str>,
+    headers: Vec<Header>,
+}
+
+// Takes the fields out of a fetched torrent, noting the ones that were asked for but didn't come
+struct FieldReader<'a> {
+    requested: &'a HashSet<String>,
+    missing: MissingFields,
+}
+
+impl FieldReader<'_> {
+    fn take<T>(
+        &mut self,
+        value: Option<T>,
+        fallback: T,
+        name: &'static str,
+        headers: &[Header],
+    ) -> T {
+        value.unwrap_or_else(|| {
+            if self.requested.contains(name) {
+                self.missing.names.push(name);
+                self.missing.headers.extend_from_slice(headers);
+            }
+            fallback
+        })
+    }
+}
+
+// Unformatted values of the columns that are kept as text, needed for sorting
+#[derive(Clone)]
+struct RawValues {
+    size_when_done: i64,
+    percent_done: f32,
+    eta_secs: i64,
+    download_speed: i64,
+    upload_speed: i64,
+    uploaded_ever: i64,
+    upload_ratio: f32,
 }
 
 impl RustmissionTorrent {
-    pub fn to_row(&self, headers: &[Header]) -> ratatui::widgets::Row {
+    // `name_width` is how wide the Name column is, if known
+    pub fn to_row(&self, headers: &[Header], name_width: Option<u16>) -> ratatui::widgets::Row<'_> {
         headers
             .iter()
-            .map(|header| self.header_to_line(*header))
+            .map(|header| match header {
+                Header::Name => self.name_line(name_width),
+                _ => self.header_to_line(*header),
+            })
             .collect::<Row>()
             .style(self.style)
     }
 
-    pub fn to_row_with_higlighted_indices(
-        &self,
-        highlighted_indices: &Vec<usize>,
-        highlight_style: Style,
-        headers: &Vec<Header>,
-    ) -> ratatui::widgets::Row {
-        let mut torrent_name_line = Line::default();
-
-        let char_indices: Vec<usize> = self.torrent_name.char_indices().map(|(i, _)| i).collect();
-        let mut last_end = 0;
-        let mut flush_line = |start: usize, end: usize| {
-            let mut start = char_indices[start];
-            let mut end = char_indices[end];
-            torrent_name_line.push_span(Span::styled(
-                &self.torrent_name[last_end..start],
-                self.style,
-            ));
-
-            while !self.torrent_name.is_char_boundary(start) {
-                start -= 1;
-            }
-
-            while !self.torrent_name.is_char_boundary(end + 1) {
-                end += 1;
+    // Name on top and the other columns underneath it, each padded to the width
+    // of its column so that they line up with the header
+    pub fn to_detailed_row<'a>(&'a self, name: Line<'a>, stat_widths: &[(Header, u16)]) -> Row<'a> {
+        let mut stats = Line::default();
+        for (header, width) in stat_widths.iter().filter(|(_, width)| *width > 0) {
+            let line = self.header_to_line(*header);
+            let padding = usize::from(*width).saturating_sub(line.width()) + 1;
+            for span in line.spans {
+                stats.push_span(span);
             }
+            stats.push_span(Span::raw(" ".repeat(padding)));
+        }
 
-            torrent_name_line.push_span(Span::styled(
-                &self.torrent_name[start..=end],
-                highlight_style,
-            ));
-            last_end = end + 1;
-        };
-
-        let mut first: Option<usize> = None;
-        let mut second: Option<usize> = None;
-        for indice in highlighted_indices {
-            let fst = if let Some(fst) = first {
-                fst
-            } else {
-                first = Some(*indice);
-                continue;
-            };
+        Row::new([Cell::from(Text::from(vec![name, stats]))])
+            .height(2)
+            .style(self.style)
+    }
 
-            let snd = if let Some(snd) = second {
-                snd
-            } else {
-                if fst + 1 == *indice {
-                    second = Some(*indice);
-                } else {
-                    flush_line(fst, fst);
-                    first = Some(*indice);
-                }
-                continue;
-            };
+    pub fn name_line(&self, width: Option<u16>) -> Line<'_> {
+        let mut spans: Vec<Span> = self.name_indicators().map(Span::raw).collect();
+        let indicators_width: usize = spans.iter().map(Span::width).sum();
 
-            if snd + 1 == *indice {
-                second = Some(*indice);
-            } else {
-                flush_line(fst, snd);
-                first = Some(*indice);
-                second = None;
+        let name = match (CONFIG.torrents_tab.name_truncation, width) {
+            (NameTruncation::Middle, Some(width)) => {
+                let max = usize::from(width).saturating_sub(indicators_width);
+                Span::raw(middle_truncated_str(&self.torrent_name, max))
             }
-        }
-
-        if let (Some(first), None) = (first, second) {
-            flush_line(first, first);
-        } else if let (Some(first), Some(second)) = (first, second) {
-            flush_line(first, second);
-        }
+            _ => Span::raw(self.torrent_name.as_str()),
+        };
+        spans.push(name);
+        Line::from(spans)
+    }
 
-        torrent_name_line.push_span(Span::styled(&self.torrent_name[last_end..], self.style));
+    pub fn to_row_with_higlighted_indices(
+        &self,
+        highlighted_indices: &[usize],
+        highlight_style: Style,
+        headers: &[Header],
+        name_width: Option<u16>,
+    ) -> ratatui::widgets::Row<'_> {
+        let mut torrent_name_line =
+            self.highlighted_name_line(highlighted_indices, highlight_style, name_width);
 
         let mut cells = vec![];
 
@@ -126,13 +162,80 @@ impl RustmissionTorrent {
         Row::new(cells)
     }
 
+    // The name with the characters that matched the filter highlighted,
+    // truncated the same way as `name_line`
+    pub fn highlighted_name_line(
+        &self,
+        highlighted_indices: &[usize],
+        highlight_style: Style,
+        width: Option<u16>,
+    ) -> Line<'_> {
+        let mut torrent_name_line = Line::default();
+        for indicator in self.name_indicators() {
+            torrent_name_line.push_span(Span::styled(indicator, self.style));
+        }
+
+        let len = self.torrent_name.chars().count();
+        let truncation = match (CONFIG.torrents_tab.name_truncation, width) {
+            (NameTruncation::Middle, Some(width)) => {
+                let max = usize::from(width).saturating_sub(torrent_name_line.width());
+                if max == 0 {
+                    return torrent_name_line;
+                }
+                middle_truncation(len, max)
+            }
+            _ => None,
+        };
+        // Chars before `gap.start` and from `gap.end` on are shown, with "…" in between
+        let gap = truncation.map_or(len..len, |(head_len, tail_len)| head_len..len - tail_len);
+
+        let style_of = |highlighted: bool| {
+            if highlighted {
+                highlight_style
+            } else {
+                self.style
+            }
+        };
+        let mut segment = String::new();
+        let mut segment_highlighted = false;
+        for (idx, c) in self.torrent_name.chars().enumerate() {
+            if gap.contains(&idx) {
+                if idx == gap.start {
+                    torrent_name_line.push_span(Span::styled(
+                        std::mem::take(&mut segment),
+                        style_of(segment_highlighted),
+                    ));
+                    torrent_name_line.push_span(Span::styled("…", self.style));
+                    segment_highlighted = false;
+                }
+                continue;
+            }
+
+            let highlighted = highlighted_indices.contains(&idx);
+            if highlighted != segment_highlighted && !segment.is_empty() {
+                torrent_name_line.push_span(Span::styled(
+                    std::mem::take(&mut segment),
+                    style_of(segment_highlighted),
+                ));
+            }
+            segment_highlighted = highlighted;
+            segment.push(c);
+        }
+        torrent_name_line.push_span(Span::styled(segment, style_of(segment_highlighted)));
+        torrent_name_line
+    }
+
     pub fn torrent_location(&self) -> String {
         format!("{}/{}", self.download_dir, self.torrent_name)
     }
 
-    fn header_to_line(&self, header: Header) -> Line {
+    fn header_to_line(&self, header: Header) -> Line<'_> {
+        if self.missing.headers.contains(&header) {
+            return Line::from("?");
+        }
+
         match header {
-            Header::Name => Line::from(self.torrent_name.as_str()),
+            Header::Name => self.name_line(None),
             Header::SizeWhenDone => Line::from(self.size_when_done.as_str()),
             Header::Progress => Line::from(self.progress.as_str()),
             Header::Eta => Line::from(self.eta_secs.as_str()),
@@ -147,8 +250,14 @@ impl RustmissionTorrent {
             Header::UploadRatio => Line::from(self.upload_ratio.as_str()),
             Header::UploadedEver => Line::from(self.uploaded_ever.as_str()),
             Header::ActivityDate => time_to_line(self.activity_date),
-            Header::AddedDate => time_to_line(self.added_date),
+            Header::AddedDate => relative_time_to_line(self.added_date),
+            Header::DoneDate => match self.done_date {
+                Some(done_date) => relative_time_to_line(done_date),
+                None => Line::default(),
+            },
             Header::PeersConnected => Line::from(self.peers_connected.to_string()),
+            Header::PeersSendingToUs => Line::from(self.peers_sending_to_us.to_string()),
+            Header::PeersGettingFromUs => Line::from(self.peers_getting_from_us.to_string()),
             Header::SmallStatus => {
                 if self.error.is_some() {
                     return Line::from("");
@@ -173,98 +282,261 @@ impl RustmissionTorrent {
         }
     }
 
+    // Glyphs shown before the name for errors, paused uploading and non-normal priorities
+    fn name_indicators(&self) -> impl Iterator<Item = &'static str> {
+        let error = self.error.is_some().then_some("⚠ ");
+        let priority = match self.bandwidth_priority {
+            Priority::High => Some("↑ "),
+            Priority::Normal => None,
+            Priority::Low => Some("↓ "),
+        };
+        let upload_paused = self.upload_paused.then_some("⊘ ");
+        error.into_iter().chain(upload_paused).chain(priority)
+    }
+
+    // Normal -> High -> Low -> Normal
+    pub const fn next_priority(&self) -> Priority {
+        match self.bandwidth_priority {
+            Priority::Normal => Priority::High,
+            Priority::High => Priority::Low,
+            Priority::Low => Priority::Normal,
+        }
+    }
+
+    // Names of the RPC fields Transmission left out for this torrent
+    pub fn missing_fields(&self) -> &[&'static str] {
+        &self.missing.names
+    }
+
+    pub const fn download_rate(&self) -> i64 {
+        self.raw.download_speed
+    }
+
+    pub const fn upload_rate(&self) -> i64 {
+        self.raw.upload_speed
+    }
+
+    pub const fn id_number(&self) -> i64 {
+        match self.id {
+            Id::Id(id) => id,
+            Id::Hash(_) => 0,
+        }
+    }
+
+    pub fn cmp_by(&self, other: &Self, header: Header) -> Ordering {
+        let (raw, other_raw) = (&self.raw, &other.raw);
+        match header {
+            Header::Name => self.torrent_name.cmp(&other.torrent_name),
+            Header::SizeWhenDone => raw.size_when_done.cmp(&other_raw.size_when_done),
+            Header::Progress => raw.percent_done.total_cmp(&other_raw.percent_done),
+            // Unknown ETAs go after the known ones
+            Header::Eta => {
+                let eta = |eta_secs: i64| if eta_secs < 0 { i64::MAX } else { eta_secs };
+                eta(raw.eta_secs).cmp(&eta(other_raw.eta_secs))
+            }
+            Header::DownloadRate => raw.download_speed.cmp(&other_raw.download_speed),
+            Header::UploadRate => raw.upload_speed.cmp(&other_raw.upload_speed),
+            Header::DownloadDir => self.download_dir.cmp(&other.download_dir),
+            Header::Padding => Ordering::Equal,
+            Header::UploadRatio => raw.upload_ratio.total_cmp(&other_raw.upload_ratio),
+            Header::UploadedEver => raw.uploaded_ever.cmp(&other_raw.uploaded_ever),
+            Header::Id => self.id_number().cmp(&other.id_number()),
+            Header::ActivityDate => self.activity_date.cmp(&other.activity_date),
+            Header::AddedDate => self.added_date.cmp(&other.added_date),
+            Header::DoneDate => self.done_date.cmp(&other.done_date),
+            Header::PeersConnected => self.peers_connected.cmp(&other.peers_connected),
+            Header::PeersSendingToUs => self.peers_sending_to_us.cmp(&other.peers_sending_to_us),
+            Header::PeersGettingFromUs => {
+                self.peers_getting_from_us.cmp(&other.peers_getting_from_us)
+            }
+            Header::SmallStatus => self.status.cmp(&other.status),
+        }
+    }
+
     pub const fn status(&self) -> TorrentStatus {
         self.status
     }
 
     pub fn update_status(&mut self, new_status: TorrentStatus) {
-        if self.error.is_some() {
-            self.style = Style::default().red().italic();
-        } else if new_status == TorrentStatus::Stopped {
-            self.style = Style::default().dark_gray().italic();
-        } else {
-            self.style = Style::default();
+        self.status = new_status;
+        self.update_style();
+    }
+
+    pub const fn is_complete(&self) -> bool {
+        self.raw.percent_done >= 1.0
+    }
+
+    pub fn set_recently_completed(&mut self, recently_completed: bool) {
+        if self.recently_completed != recently_completed {
+            self.recently_completed = recently_completed;
+            self.update_style();
         }
+    }
 
-        self.status = new_status;
+    fn update_style(&mut self) {
+        self.style = torrent_style(self.status, self.error.is_some());
+        if self.recently_completed {
+            self.style = self.style.patch(completed_style());
+        }
     }
 }
 
-impl From<Torrent> for RustmissionTorrent {
-    fn from(t: Torrent) -> Self {
-        let id = t.id().expect("id requested");
+impl RustmissionTorrent {
+    // `requested` has the names of the fields that were asked for
+    pub fn new(t: Torrent, requested: &HashSet<String>) -> Self {
+        let mut missing = FieldReader {
+            requested,
+            missing: MissingFields::default(),
+        };
+
+        let hash = missing.take(t.hash_string.clone(), String::new(), "hashString", &[]);
 
-        let torrent_name = t.name.clone().expect("name requested");
+        let id = match t.id() {
+            Some(id) => id,
+            None => missing.take(None, Id::Hash(hash.clone()), "id", &[Header::Id]),
+        };
+
+        let torrent_name = missing.take(t.name.clone(), "?".to_string(), "name", &[]);
+
+        let units = CONFIG.general.units;
+
+        let raw = RawValues {
+            size_when_done: missing.take(
+                t.size_when_done,
+                0,
+                "sizeWhenDone",
+                &[Header::SizeWhenDone],
+            ),
+            percent_done: missing.take(t.percent_done, 0.0, "percentDone", &[Header::Progress]),
+            eta_secs: missing.take(t.eta, -1, "eta", &[Header::Eta]),
+            download_speed: missing.take(
+                t.rate_download,
+                0,
+                "rateDownload",
+                &[Header::DownloadRate],
+            ),
+            upload_speed: missing.take(t.rate_upload, 0, "rateUpload", &[Header::UploadRate]),
+            uploaded_ever: missing.take(
+                t.uploaded_ever,
+                0,
+                "uploadedEver",
+                &[Header::UploadedEver],
+            ),
+            upload_ratio: missing.take(t.upload_ratio, 0.0, "uploadRatio", &[Header::UploadRatio]),
+        };
 
-        let size_when_done = bytes_to_human_format(t.size_when_done.expect("field requested"));
+        let size_when_done = units.format_size(raw.size_when_done);
 
-        let progress = match t.percent_done.expect("field requested") {
-            done if done == 1f32 => String::default(),
+        let progress = match raw.percent_done {
+            1f32 => String::default(),
             percent => format!("{:.2}%", percent * 100f32),
         };
 
-        let eta_secs = match t.eta.expect("field requested") {
-            -2 => "∞".to_string(),
-            -1 => String::default(),
-            eta_secs => seconds_to_human_format(eta_secs),
-        };
+        let status = missing.take(
+            t.status,
+            TorrentStatus::Stopped,
+            "status",
+            &[Header::SmallStatus],
+        );
 
-        let download_speed = match t.rate_download.expect("field requested") {
+        // A download that stopped making progress, while still being active
+        let is_stalled = status == TorrentStatus::Downloading
+            && missing.take(t.is_stalled, false, "isStalled", &[Header::Eta]);
+        let eta_secs = eta_format(raw.eta_secs, is_stalled);
+
+        let download_speed = match raw.download_speed {
             0 => String::default(),
-            down => bytes_to_human_format(down),
+            down => units.format_speed(down),
         };
 
-        let upload_speed = match t.rate_upload.expect("field requested") {
+        let upload_speed = match raw.upload_speed {
             0 => String::default(),
-            upload => bytes_to_human_format(upload),
+            upload => units.format_speed(upload),
         };
 
-        let status = t.status.expect("field requested");
-
-        let download_dir = t.download_dir.clone().expect("field requested");
+        let download_dir = missing.take(
+            t.download_dir.clone(),
+            String::new(),
+            "downloadDir",
+            &[Header::DownloadDir],
+        );
 
-        let uploaded_ever = bytes_to_human_format(t.uploaded_ever.expect("field requested"));
+        let uploaded_ever = units.format_size(raw.uploaded_ever);
 
-        let upload_ratio = {
-            let raw = t.upload_ratio.expect("field requested");
-            format!("{:.1}", raw)
-        };
+        let upload_ratio = format!("{:.1}", raw.upload_ratio);
 
         let activity_date = {
-            let raw = t.activity_date.expect("field requested");
+            let raw = missing.take(t.activity_date, 0, "activityDate", &[Header::ActivityDate]);
             chrono::DateTime::from_timestamp(raw, 0)
-                .unwrap()
+                .unwrap_or_default()
                 .naive_local()
         };
 
         let added_date = {
-            let raw = t.added_date.expect("field requested");
+            let raw = missing.take(t.added_date, 0, "addedDate", &[Header::AddedDate]);
             chrono::DateTime::from_timestamp(raw, 0)
-                .unwrap()
+                .unwrap_or_default()
                 .naive_local()
         };
 
-        let peers_connected = t.peers_connected.expect("field requested");
-
-        let error = {
-            if t.error.expect("field requested") != ErrorType::Ok {
-                Some(t.error_string.expect("field requested"))
-            } else {
+        let done_date = {
+            let raw = missing.take(t.done_date, 0, "doneDate", &[Header::DoneDate]);
+            // 0 means that the torrent isn't done yet
+            if raw == 0 {
                 None
+            } else {
+                chrono::DateTime::from_timestamp(raw, 0).map(|date| date.naive_local())
             }
         };
 
-        let style = {
-            if error.is_some() {
-                Style::default().red().italic()
+        let peers_connected = missing.take(
+            t.peers_connected,
+            0,
+            "peersConnected",
+            &[Header::PeersConnected],
+        );
+
+        let peers_sending_to_us = missing.take(
+            t.peers_sending_to_us,
+            0,
+            "peersSendingToUs",
+            &[Header::PeersSendingToUs],
+        );
+
+        let peers_getting_from_us = missing.take(
+            t.peers_getting_from_us,
+            0,
+            "peersGettingFromUs",
+            &[Header::PeersGettingFromUs],
+        );
+
+        // Transmission before 3.0 doesn't know about labels
+        let labels = t.labels.unwrap_or_default();
+
+        let bandwidth_priority = missing.take(
+            t.bandwidth_priority,
+            Priority::Normal,
+            "bandwidthPriority",
+            &[],
+        );
+
+        let tracker_host = t
+            .trackers
+            .as_ref()
+            .and_then(|trackers| trackers.first())
+            .and_then(|tracker| reqwest::Url::parse(&tracker.announce).ok())
+            .and_then(|url| url.host_str().map(str::to_string));
+
+        let error = {
+            if missing.take(t.error, ErrorType::Ok, "error", &[]) != ErrorType::Ok {
+                Some(missing.take(t.error_string, "?".to_string(), "errorString", &[]))
             } else {
-                match status {
-                    TorrentStatus::Stopped => Style::default().dark_gray().italic(),
-                    _ => Style::default(),
-                }
+                None
             }
         };
 
+        let style = torrent_style(status, error.is_some());
+
         Self {
             torrent_name,
             size_when_done,
@@ -275,17 +547,46 @@ impl From<Torrent> for RustmissionTorrent {
             status,
             style,
             id,
+            hash,
             download_dir,
             uploaded_ever,
             upload_ratio,
             activity_date,
             added_date,
+            done_date,
             peers_connected,
+            peers_sending_to_us,
+            peers_getting_from_us,
             error,
+            labels,
+            bandwidth_priority,
+            tracker_host,
+            upload_paused: false,
+            recently_completed: false,
+            missing: missing.missing,
+            raw,
         }
     }
 }
 
+fn torrent_style(status: TorrentStatus, has_error: bool) -> Style {
+    let theme = &CONFIG.theme;
+    if has_error {
+        return Style::default().fg(theme.error).italic();
+    }
+
+    match status {
+        TorrentStatus::Stopped => Style::default().fg(theme.paused).italic(),
+        TorrentStatus::Downloading => Style::default().fg(theme.downloading),
+        TorrentStatus::Seeding => Style::default().fg(theme.seeding),
+        _ => Style::default(),
+    }
+}
+
+fn completed_style() -> Style {
+    Style::default().fg(CONFIG.theme.seeding).bold().reversed()
+}
+
 fn time_to_line<'a>(time: NaiveDateTime) -> Line<'a> {
     let today = chrono::Local::now();
     if time.year() == today.year() && time.month() == today.month() && time.day() == today.day() {
@@ -294,3 +595,8 @@ fn time_to_line<'a>(time: NaiveDateTime) -> Line<'a> {
         Line::from(time.format("%y|%m|%d %H:%M").to_string())
     }
 }
+
+fn relative_time_to_line<'a>(time: NaiveDateTime) -> Line<'a> {
+    let elapsed = chrono::Utc::now().naive_utc() - time;
+    Line::from(seconds_to_relative_format(elapsed.num_seconds()))
+}
@@ -1,19 +1,31 @@
 use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use ratatui::{prelude::*, widgets::Row};
-use rm_config::CONFIG;
-use rm_shared::header::Header;
-use std::collections::HashMap;
+use rm_config::{state::State, CONFIG};
+use rm_shared::{
+    header::Header,
+    sort::{SortDirection, SortKey, SORT_PRESETS},
+    utils::{CaseMatching, RowLayout},
+};
+use std::{collections::HashMap, ops::Range};
+use transmission_rpc::types::{Id, TorrentGetField};
 
 use crate::tui::components::GenericTable;
 
 use super::rustmission_torrent::RustmissionTorrent;
+use crate::transmission::columns::torrent_fields;
 
 pub struct TableManager {
     pub table: GenericTable<RustmissionTorrent>,
     pub widths: Vec<Constraint>,
     pub filter: Option<Filter>,
+    pub label_filter: Option<String>,
+    pub case_matching: CaseMatching,
+    pub sort: Option<SortKey>,
     pub torrents_displaying_no: u16,
-    headers: Vec<&'static str>,
+    pub table_rect: Rect,
+    available_width: u16,
+    columns: Vec<Header>,
+    row_layout: RowLayout,
 }
 
 pub struct Filter {
@@ -25,18 +37,27 @@ pub struct Filter {
 impl TableManager {
     pub fn new() -> Self {
         let table = GenericTable::new(vec![]);
-        let widths = Self::default_widths(&CONFIG.torrents_tab.headers);
-        let mut headers = vec![];
-        for header in &CONFIG.torrents_tab.headers {
-            headers.push(header.header_name());
-        }
+        let state = State::load();
+        let columns = state
+            .columns
+            .unwrap_or_else(|| CONFIG.torrents_tab.headers.clone());
+        let widths = Self::default_widths(&columns);
 
         Self {
             table,
             widths,
             filter: None,
+            label_filter: None,
+            case_matching: state
+                .case_matching
+                .unwrap_or(CONFIG.torrents_tab.filter_case),
+            // The sort picked last time only applies when there's no default one
+            sort: CONFIG.torrents_tab.default_sort.or(state.sort),
             torrents_displaying_no: 0,
-            headers,
+            table_rect: Rect::default(),
+            available_width: 0,
+            columns,
+            row_layout: CONFIG.torrents_tab.row_layout,
         }
     }
 
@@ -44,69 +65,308 @@ impl TableManager {
         if let Some(filter) = &self.filter {
             self.table.overwrite_len(filter.indexes.len());
         } else {
-            self.table.items.len();
+            *self.table.overwritten_len.borrow_mut() = None;
         }
+        self.table.clamp_selection();
     }
 
-    pub fn rows(&self) -> Vec<Row<'_>> {
-        if let Some(filter) = &self.filter {
-            let highlight_style = Style::default().fg(CONFIG.general.accent_color);
-            let headers = &CONFIG.torrents_tab.headers;
-            let mut rows = vec![];
-            for (i, which_torrent) in filter.indexes.iter().enumerate() {
-                let row = self.table.items[*which_torrent as usize].to_row_with_higlighted_indices(
-                    &filter.highlight_indices[i],
-                    highlight_style,
-                    headers,
-                );
-                rows.push(row);
+    // Builds only the rows within `range`, as building all of them gets slow with
+    // thousands of torrents while only a screenful is ever visible.
+    pub fn rows(&self, range: Range<usize>) -> Vec<Row<'_>> {
+        let highlight_style = Style::default().fg(CONFIG.general.accent_color);
+        let name_width = self.name_width();
+        let stat_widths = self.stat_widths();
+
+        range
+            .map(|i| {
+                let (torrent, highlighted) = match &self.filter {
+                    Some(filter) => (
+                        &self.table.items[filter.indexes[i] as usize],
+                        Some(&filter.highlight_indices[i]),
+                    ),
+                    None => (&self.table.items[i], None),
+                };
+
+                match (self.row_layout, highlighted) {
+                    (RowLayout::Compact, Some(indices)) => torrent.to_row_with_higlighted_indices(
+                        indices,
+                        highlight_style,
+                        &self.columns,
+                        name_width,
+                    ),
+                    (RowLayout::Compact, None) => torrent.to_row(&self.columns, name_width),
+                    (RowLayout::Detailed, Some(indices)) => torrent.to_detailed_row(
+                        torrent.highlighted_name_line(
+                            indices,
+                            highlight_style,
+                            Some(self.available_width),
+                        ),
+                        &stat_widths,
+                    ),
+                    (RowLayout::Detailed, None) => torrent.to_detailed_row(
+                        torrent.name_line(Some(self.available_width)),
+                        &stat_widths,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    pub fn toggle_row_layout(&mut self) {
+        self.row_layout = match self.row_layout {
+            RowLayout::Compact => RowLayout::Detailed,
+            RowLayout::Detailed => RowLayout::Compact,
+        };
+    }
+
+    // How many lines of the table each torrent takes up
+    pub const fn row_height(&self) -> u16 {
+        match self.row_layout {
+            RowLayout::Compact => 1,
+            RowLayout::Detailed => 2,
+        }
+    }
+
+    pub fn table_widths(&self) -> Vec<Constraint> {
+        match self.row_layout {
+            RowLayout::Compact => self.widths.clone(),
+            RowLayout::Detailed => vec![Constraint::Percentage(100)],
+        }
+    }
+
+    pub fn header_row(&self) -> Row<'_> {
+        let headers = self.headers();
+        match self.row_layout {
+            RowLayout::Compact => Row::new(headers),
+            RowLayout::Detailed => {
+                // Only the columns that are laid out under the name
+                let mut line = String::new();
+                for ((header, width), name) in
+                    self.columns.iter().zip(self.column_widths()).zip(headers)
+                {
+                    if *header == Header::Name || width == 0 {
+                        continue;
+                    }
+                    let padding = usize::from(width).saturating_sub(Span::raw(&name).width()) + 1;
+                    line.push_str(&name);
+                    line.push_str(&" ".repeat(padding));
+                }
+                Row::new([line])
             }
+        }
+    }
 
-            self.table.overwrite_len(rows.len());
-            rows
-        } else {
-            self.table
-                .items
-                .iter()
-                .map(|t| t.to_row(&CONFIG.torrents_tab.headers))
-                .collect()
+    fn column_widths(&self) -> impl Iterator<Item = u16> + '_ {
+        self.widths.iter().map(|constraint| match constraint {
+            Constraint::Length(width) | Constraint::Max(width) => *width,
+            _ => 0,
+        })
+    }
+
+    // Widths of every column but Name, for the detailed layout
+    fn stat_widths(&self) -> Vec<(Header, u16)> {
+        self.columns
+            .iter()
+            .copied()
+            .zip(self.column_widths())
+            .filter(|(header, _)| *header != Header::Name)
+            .collect()
+    }
+
+    fn name_width(&self) -> Option<u16> {
+        let name_idx = self
+            .columns
+            .iter()
+            .position(|header| *header == Header::Name)?;
+        match self.widths.get(name_idx)? {
+            Constraint::Length(width) | Constraint::Max(width) => Some(*width),
+            _ => None,
         }
     }
 
-    pub const fn headers(&self) -> &Vec<&'static str> {
-        &self.headers
+    fn headers(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|header| match self.sort {
+                Some(sort) if sort.header == *header => {
+                    format!("{} {}", header.header_name(), sort.direction.arrow())
+                }
+                _ => header.header_name().to_string(),
+            })
+            .collect()
     }
 
-    pub fn current_torrent(&mut self) -> Option<&mut RustmissionTorrent> {
+    pub fn torrent_fields(&self, with_trackers: bool) -> Vec<TorrentGetField> {
+        torrent_fields(
+            &self.columns,
+            self.sort.map(|sort| sort.header),
+            with_trackers,
+        )
+    }
+
+    pub fn columns(&self) -> &[Header] {
+        &self.columns
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<Header>) {
+        self.columns = columns;
+        self.widths = self.header_widths(&self.table.items);
+    }
+
+    // Maps the highlighted row onto its index in `table.items`, going through the
+    // filter if there's one. Anything acting on the selected torrent must go through
+    // this, so that it never targets a different torrent than the highlighted one.
+    fn selected_index(&self) -> Option<usize> {
         let selected_idx = self.table.state.borrow().selected()?;
 
+        match &self.filter {
+            Some(filter) => filter.indexes.get(selected_idx).map(|idx| *idx as usize),
+            None => (selected_idx < self.table.items.len()).then_some(selected_idx),
+        }
+    }
+
+    pub fn selected_torrent(&self) -> Option<&RustmissionTorrent> {
+        self.table.items.get(self.selected_index()?)
+    }
+
+    pub fn selected_torrent_mut(&mut self) -> Option<&mut RustmissionTorrent> {
+        let idx = self.selected_index()?;
+        self.table.items.get_mut(idx)
+    }
+
+    pub fn visible_torrents(&mut self) -> Vec<&mut RustmissionTorrent> {
         if let Some(filter) = &self.filter {
-            if filter.indexes.is_empty() {
-                None
-            } else {
-                self.table
-                    .items
-                    .get_mut(filter.indexes[selected_idx] as usize)
-            }
+            self.table
+                .items
+                .iter_mut()
+                .enumerate()
+                .filter(|(i, _)| filter.indexes.contains(&(*i as u16)))
+                .map(|(_, torrent)| torrent)
+                .collect()
         } else {
-            self.table.items.get_mut(selected_idx)
+            self.table.items.iter_mut().collect()
         }
     }
 
     pub fn set_new_rows(&mut self, rows: Vec<RustmissionTorrent>) {
         self.table.set_items(rows);
+        self.sort_rows();
         self.widths = self.header_widths(&self.table.items);
+        if let Some(filter) = &self.filter {
+            self.set_filter(filter.pattern.clone());
+        }
         self.update_rows_number();
     }
 
-    pub fn set_filter(&mut self, filter: String) {
+    // Sorts by the current key, falling back to the ID so that torrents with
+    // equal keys don't swap places on every refresh.
+    fn sort_rows(&mut self) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+
+        self.table.items.sort_by(|a, b| {
+            let ordering = a.cmp_by(b, sort.header);
+            let ordering = match sort.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            };
+            ordering.then_with(|| a.id_number().cmp(&b.id_number()))
+        });
+    }
+
+    // Changes the sort while keeping the same torrent selected.
+    pub fn set_sort(&mut self, sort: Option<SortKey>) {
+        let selected_id = self.selected_torrent().map(|torrent| torrent.id_number());
+
+        self.sort = sort;
+        State::remember_sort(sort);
+        self.sort_rows();
+        if let Some(filter) = &self.filter {
+            self.set_filter(filter.pattern.clone());
+        }
+
+        if let Some(id) = selected_id {
+            self.select_by_id(id);
+        }
+    }
+
+    // Sorts by the next displayed column, or not at all after the last one.
+    pub fn sort_by_next_column(&mut self) {
+        let headers: Vec<Header> = self
+            .columns
+            .iter()
+            .copied()
+            .filter(|header| *header != Header::Padding)
+            .collect();
+
+        let next_idx = match self.sort {
+            Some(sort) => headers
+                .iter()
+                .position(|header| *header == sort.header)
+                .map_or(0, |idx| idx + 1),
+            None => 0,
+        };
+
+        let direction = self
+            .sort
+            .map_or(SortDirection::Ascending, |sort| sort.direction);
+        let sort = headers
+            .get(next_idx)
+            .map(|header| SortKey::new(*header, direction));
+        self.set_sort(sort);
+    }
+
+    // Switches to the preset after the current one and returns its name.
+    pub fn next_sort_preset(&mut self) -> &'static str {
+        let next_idx = SORT_PRESETS
+            .iter()
+            .position(|preset| Some(preset.key) == self.sort)
+            .map_or(0, |idx| (idx + 1) % SORT_PRESETS.len());
+
+        let preset = &SORT_PRESETS[next_idx];
+        self.set_sort(Some(preset.key));
+        preset.name
+    }
+
+    pub fn reverse_sort(&mut self) {
+        if let Some(sort) = self.sort {
+            self.set_sort(Some(SortKey::new(sort.header, sort.direction.reversed())));
+        }
+    }
+
+    pub fn set_available_width(&mut self, width: u16) {
+        if self.available_width != width {
+            self.available_width = width;
+            self.widths = self.header_widths(&self.table.items);
+        }
+    }
+
+    fn matcher(&self) -> SkimMatcherV2 {
         let matcher = SkimMatcherV2::default();
+        match self.case_matching {
+            CaseMatching::Smart => matcher.smart_case(),
+            CaseMatching::Ignore => matcher.ignore_case(),
+            CaseMatching::Respect => matcher.respect_case(),
+        }
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        let matcher = self.matcher();
         let mut indexes: Vec<u16> = vec![];
         let mut highlight_indices = vec![];
         for (i, torrent) in self.table.items.iter().enumerate() {
-            if let Some((_, indices)) = matcher.fuzzy_indices(&torrent.torrent_name, &filter) {
-                indexes.push(i as u16);
-                highlight_indices.push(indices);
+            if let Some(label) = &self.label_filter {
+                if !torrent.labels.contains(label) {
+                    continue;
+                }
+            }
+
+            if let Some((score, indices)) = matcher.fuzzy_indices(&torrent.torrent_name, &filter) {
+                if strong_enough(score, &filter) {
+                    indexes.push(i as u16);
+                    highlight_indices.push(indices);
+                }
             }
         }
 
@@ -119,6 +379,133 @@ impl TableManager {
         self.filter = Some(filter);
     }
 
+    pub fn clear_filter(&mut self) {
+        if self.label_filter.is_some() {
+            self.set_filter(String::new());
+        } else {
+            self.filter = None;
+        }
+    }
+
+    pub fn set_label_filter(&mut self, label: Option<String>) {
+        self.label_filter = label;
+
+        let pattern = self
+            .filter
+            .as_ref()
+            .map(|filter| filter.pattern.clone())
+            .unwrap_or_default();
+
+        if self.label_filter.is_none() && pattern.is_empty() {
+            self.filter = None;
+        } else {
+            self.set_filter(pattern);
+        }
+    }
+
+    // Drops the text and label filters and goes back to the top.
+    // Returns whether there was anything to reset.
+    pub fn reset_view(&mut self) -> bool {
+        let at_top = {
+            let state = self.table.state.borrow();
+            state.offset() == 0 && state.selected().unwrap_or_default() == 0
+        };
+        if self.filter.is_none() && self.label_filter.is_none() && at_top {
+            return false;
+        }
+
+        self.filter = None;
+        self.label_filter = None;
+        self.update_rows_number();
+        *self.table.state.borrow_mut().offset_mut() = 0;
+        self.table.scroll_to_home();
+        true
+    }
+
+    // Switches to the next label (in alphabetical order) used by any torrent,
+    // going back to no label filter after the last one.
+    pub fn cycle_label_filter(&mut self) {
+        let mut labels: Vec<&String> = self
+            .table
+            .items
+            .iter()
+            .flat_map(|torrent| &torrent.labels)
+            .collect();
+        labels.sort();
+        labels.dedup();
+
+        let next_label = match &self.label_filter {
+            None => labels.first(),
+            Some(current) => labels
+                .iter()
+                .position(|label| *label == current)
+                .and_then(|idx| labels.get(idx + 1)),
+        };
+
+        self.set_label_filter(next_label.map(|label| label.to_string()));
+    }
+
+    // Maps a terminal position onto the index of the visible row that's rendered there.
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let header_height = u16::from(!CONFIG.general.headers_hide);
+        let rect = self.table_rect;
+        if column < rect.x
+            || column >= rect.right()
+            || row < rect.y + header_height
+            || row >= rect.bottom()
+        {
+            return None;
+        }
+
+        let offset = self.table.state.borrow().offset();
+        let idx = offset + usize::from((row - rect.y - header_height) / self.row_height());
+        (idx < self.table.get_len()).then_some(idx)
+    }
+
+    // Selects the torrent with this ID, as long as it isn't filtered out.
+    pub fn select_by_id(&mut self, id: i64) -> bool {
+        let has_id = |torrent: &RustmissionTorrent| matches!(torrent.id, Id::Id(i) if i == id);
+        let position = if let Some(filter) = &self.filter {
+            filter
+                .indexes
+                .iter()
+                .position(|idx| has_id(&self.table.items[*idx as usize]))
+        } else {
+            self.table.items.iter().position(has_id)
+        };
+
+        if let Some(position) = position {
+            self.table.state.borrow_mut().select(Some(position));
+        }
+        position.is_some()
+    }
+
+    // Selects the first visible torrent matching the pattern without touching the filter.
+    pub fn jump_to(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            return;
+        }
+
+        let matcher = self.matcher();
+        let is_match = |torrent: &RustmissionTorrent| {
+            matcher
+                .fuzzy_match(&torrent.torrent_name, pattern)
+                .is_some_and(|score| strong_enough(score, pattern))
+        };
+        let position = if let Some(filter) = &self.filter {
+            filter
+                .indexes
+                .iter()
+                .position(|idx| is_match(&self.table.items[*idx as usize]))
+        } else {
+            self.table.items.iter().position(is_match)
+        };
+
+        if let Some(position) = position {
+            self.table.state.borrow_mut().select(Some(position));
+        }
+    }
+
     fn default_widths(headers: &Vec<Header>) -> Vec<Constraint> {
         let mut constraints = vec![];
 
@@ -129,10 +516,12 @@ impl TableManager {
     }
 
     fn header_widths(&self, rows: &[RustmissionTorrent]) -> Vec<Constraint> {
-        let headers = &CONFIG.torrents_tab.headers;
+        let headers = &self.columns;
 
         if !CONFIG.general.auto_hide {
-            return Self::default_widths(headers);
+            let mut constraints = Self::default_widths(headers);
+            self.expand_name_width(&mut constraints);
+            return constraints;
         }
 
         let mut map = HashMap::new();
@@ -146,6 +535,10 @@ impl TableManager {
             Header::UploadRate,
             Header::DownloadRate,
             Header::Eta,
+            Header::DoneDate,
+            Header::PeersConnected,
+            Header::PeersSendingToUs,
+            Header::PeersGettingFromUs,
         ];
 
         for hidable_header in &hidable_headers {
@@ -171,6 +564,26 @@ impl TableManager {
                 map.entry(&Header::Eta)
                     .and_modify(|c| *c = Header::Eta.default_constraint());
             }
+
+            if row.done_date.is_some() {
+                map.entry(&Header::DoneDate)
+                    .and_modify(|c| *c = Header::DoneDate.default_constraint());
+            }
+
+            if row.peers_connected > 0 {
+                map.entry(&Header::PeersConnected)
+                    .and_modify(|c| *c = Header::PeersConnected.default_constraint());
+            }
+
+            if row.peers_sending_to_us > 0 {
+                map.entry(&Header::PeersSendingToUs)
+                    .and_modify(|c| *c = Header::PeersSendingToUs.default_constraint());
+            }
+
+            if row.peers_getting_from_us > 0 {
+                map.entry(&Header::PeersGettingFromUs)
+                    .and_modify(|c| *c = Header::PeersGettingFromUs.default_constraint());
+            }
         }
 
         let mut constraints = vec![];
@@ -179,6 +592,162 @@ impl TableManager {
             constraints.push(map.remove(header).expect("this header exists"))
         }
 
+        self.expand_name_width(&mut constraints);
         constraints
     }
+
+    // Give the Name column all the space that's left after the other columns.
+    fn expand_name_width(&self, constraints: &mut [Constraint]) {
+        if self.available_width == 0 {
+            return;
+        }
+
+        let headers = &self.columns;
+        let Some(name_idx) = headers.iter().position(|header| *header == Header::Name) else {
+            return;
+        };
+
+        let mut taken_width = 0u16;
+        for (idx, constraint) in constraints.iter().enumerate() {
+            if idx == name_idx {
+                continue;
+            }
+
+            // + 1 for column spacing
+            taken_width = taken_width.saturating_add(1);
+            if let Constraint::Length(len) | Constraint::Max(len) = constraint {
+                taken_width = taken_width.saturating_add(*len);
+            }
+        }
+
+        constraints[name_idx] =
+            Constraint::Length(self.available_width.saturating_sub(taken_width));
+    }
+}
+
+// Loose matches (letters scattered all over the name) score low for how long
+// the pattern is, so they're left out.
+fn strong_enough(score: i64, pattern: &str) -> bool {
+    let pattern_len = i64::try_from(pattern.chars().count()).unwrap_or(i64::MAX);
+    score
+        >= CONFIG
+            .torrents_tab
+            .filter_threshold
+            .saturating_mul(pattern_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use rm_shared::{
+        header::Header,
+        sort::{SortDirection, SortKey},
+    };
+
+    use super::TableManager;
+    use crate::tui::tabs::torrents::test_utils::{sandbox, torrent};
+
+    fn ids(table_manager: &TableManager) -> Vec<i64> {
+        table_manager
+            .table
+            .items
+            .iter()
+            .map(|torrent| torrent.id_number())
+            .collect()
+    }
+
+    #[test]
+    fn sort_doesnt_depend_on_input_order() {
+        sandbox();
+        let torrents = vec![
+            torrent(1, "b", 100),
+            torrent(2, "a", 200),
+            torrent(3, "c", 100),
+            torrent(4, "d", 200),
+            torrent(5, "e", 50),
+        ];
+        let sort = Some(SortKey::new(
+            Header::SizeWhenDone,
+            SortDirection::Descending,
+        ));
+
+        let mut forward = TableManager::new();
+        forward.set_sort(sort);
+        forward.set_new_rows(torrents.clone());
+
+        let mut backward = TableManager::new();
+        backward.set_new_rows(torrents.into_iter().rev().collect());
+        backward.set_sort(sort);
+
+        assert_eq!(ids(&forward), [2, 4, 1, 3, 5]);
+        assert_eq!(ids(&forward), ids(&backward));
+    }
+
+    #[test]
+    fn selected_torrent_goes_through_the_filter() {
+        sandbox();
+        let mut table_manager = TableManager::new();
+        table_manager.set_sort(None);
+        table_manager.set_new_rows(vec![
+            torrent(1, "debian", 100),
+            torrent(2, "ubuntu", 100),
+            torrent(3, "fedora", 100),
+            torrent(4, "ubuntu server", 100),
+        ]);
+        table_manager.set_filter("ubuntu".to_string());
+        table_manager.update_rows_number();
+        table_manager.table.state.borrow_mut().select(Some(1));
+
+        let selected = table_manager.selected_torrent().map(|t| t.id_number());
+        assert_eq!(selected, Some(4));
+        let selected = table_manager.selected_torrent_mut().map(|t| t.id_number());
+        assert_eq!(selected, Some(4));
+    }
+
+    #[test]
+    fn selection_is_clamped_after_filtering() {
+        sandbox();
+        let mut table_manager = TableManager::new();
+        table_manager.set_sort(None);
+        let torrents: Vec<_> = (0..30)
+            .map(|id| {
+                let name = if id % 6 == 5 { "ubuntu" } else { "debian" };
+                torrent(id, &format!("{name} {id}"), 100)
+            })
+            .collect();
+        table_manager.set_new_rows(torrents.clone());
+        table_manager.table.state.borrow_mut().select(Some(20));
+
+        table_manager.set_filter("ubuntu".to_string());
+        table_manager.set_new_rows(torrents);
+
+        assert_eq!(table_manager.table.state.borrow().selected(), Some(4));
+        let selected = table_manager.selected_torrent().map(|t| t.id_number());
+        assert_eq!(selected, Some(29));
+    }
+
+    #[test]
+    fn only_visible_rows_are_built() {
+        sandbox();
+        let mut table_manager = TableManager::new();
+        table_manager.set_sort(None);
+        table_manager.set_new_rows(
+            (0..10_000)
+                .map(|id| torrent(id, &format!("torrent {id}"), 100))
+                .collect(),
+        );
+
+        let range = table_manager.table.visible_range(40);
+        assert_eq!(range, 0..40);
+        assert_eq!(table_manager.rows(range).len(), 40);
+
+        table_manager.table.state.borrow_mut().select(Some(5000));
+        let range = table_manager.table.visible_range(40);
+        assert_eq!(range, 4961..5001);
+        assert_eq!(table_manager.rows(range).len(), 40);
+
+        table_manager.table.scroll_to_end();
+        let range = table_manager.table.visible_range(40);
+        assert_eq!(range, 9960..10_000);
+        assert_eq!(table_manager.rows(range).len(), 40);
+    }
 }
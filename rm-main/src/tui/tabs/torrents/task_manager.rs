@@ -1,10 +1,14 @@
+use std::{collections::VecDeque, time::Duration};
+
 use ratatui::prelude::*;
 use throbber_widgets_tui::ThrobberState;
 use tokio::time::Instant;
+use transmission_rpc::types::Id;
 
 use rm_shared::{
     action::{Action, UpdateAction},
     status_task::StatusTask,
+    utils::CaseMatching,
 };
 
 use crate::tui::{
@@ -19,8 +23,11 @@ use super::{
         add_magnet::AddMagnetBar,
         default::DefaultBar,
         delete_torrent::{self, DeleteBar, TorrentInfo},
+        export::{ExportBar, ExportRecord},
         filter::FilterBar,
+        label::LabelBar,
         move_torrent::MoveBar,
+        quick_jump::QuickJumpBar,
         status::{CurrentTaskState, StatusBar},
     },
 };
@@ -28,12 +35,18 @@ use super::{
 pub struct TaskManager {
     ctx: app::Ctx,
     current_task: CurrentTask,
+    // Status messages waiting for the current task to go away, oldest first
+    status_queue: VecDeque<StatusBar>,
 }
 
+// How long a finished status stays when there are more messages waiting
+const QUEUED_STATUS_DURATION: Duration = Duration::from_millis(1500);
+
 impl TaskManager {
     pub fn new(ctx: app::Ctx) -> Self {
         Self {
             current_task: CurrentTask::Default(DefaultBar::new()),
+            status_queue: VecDeque::new(),
             ctx,
         }
     }
@@ -42,8 +55,11 @@ impl TaskManager {
 pub enum CurrentTask {
     AddMagnetBar(AddMagnetBar),
     DeleteBar(DeleteBar),
+    ExportBar(ExportBar),
     FilterBar(FilterBar),
+    LabelBar(LabelBar),
     MoveBar(MoveBar),
+    QuickJumpBar(QuickJumpBar),
     Default(DefaultBar),
     Status(StatusBar),
 }
@@ -57,37 +73,23 @@ impl CurrentTask {
 }
 
 impl Component for TaskManager {
-    #[must_use]
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
-        match &mut self.current_task {
-            CurrentTask::AddMagnetBar(magnet_bar) => {
-                if magnet_bar.handle_actions(action).is_quit() {
-                    self.cancel_task()
-                }
-            }
-            CurrentTask::DeleteBar(delete_bar) => {
-                if delete_bar.handle_actions(action).is_quit() {
-                    self.cancel_task()
-                }
-            }
-            CurrentTask::MoveBar(move_bar) => {
-                if move_bar.handle_actions(action).is_quit() {
-                    self.cancel_task()
-                }
-            }
-            CurrentTask::FilterBar(filter_bar) => {
-                if filter_bar.handle_actions(action).is_quit() {
-                    self.cancel_task()
-                }
-            }
-            CurrentTask::Status(status_bar) => {
-                if status_bar.handle_actions(action).is_quit() {
-                    self.cancel_task()
-                }
-            }
-
-            _ => (),
+        let component_action = match &mut self.current_task {
+            CurrentTask::AddMagnetBar(magnet_bar) => magnet_bar.handle_actions(action),
+            CurrentTask::DeleteBar(delete_bar) => delete_bar.handle_actions(action),
+            CurrentTask::ExportBar(export_bar) => export_bar.handle_actions(action),
+            CurrentTask::MoveBar(move_bar) => move_bar.handle_actions(action),
+            CurrentTask::FilterBar(filter_bar) => filter_bar.handle_actions(action),
+            CurrentTask::LabelBar(label_bar) => label_bar.handle_actions(action),
+            CurrentTask::QuickJumpBar(jump_bar) => jump_bar.handle_actions(action),
+            CurrentTask::Status(status_bar) => status_bar.handle_actions(action),
+            CurrentTask::Default(_) => ComponentAction::Nothing,
         };
+
+        if component_action.is_quit() {
+            self.cancel_task()
+        }
+
         ComponentAction::Nothing
     }
 
@@ -97,12 +99,12 @@ impl Component for TaskManager {
             UpdateAction::TaskSet(task) => self.pending_task(task),
             UpdateAction::TaskSetSuccess(task) => self.success_task(task),
             UpdateAction::TaskSuccess => {
-                if let CurrentTask::Status(status_bar) = &mut self.current_task {
+                if let Some(status_bar) = self.loading_status() {
                     status_bar.set_success();
                 }
             }
             UpdateAction::TaskFailure => {
-                if let CurrentTask::Status(status_bar) = &mut self.current_task {
+                if let Some(status_bar) = self.loading_status() {
                     status_bar.set_failure();
                 }
             }
@@ -114,15 +116,25 @@ impl Component for TaskManager {
         match &mut self.current_task {
             CurrentTask::AddMagnetBar(magnet_bar) => magnet_bar.render(f, rect),
             CurrentTask::DeleteBar(delete_bar) => delete_bar.render(f, rect),
+            CurrentTask::ExportBar(export_bar) => export_bar.render(f, rect),
             CurrentTask::MoveBar(move_bar) => move_bar.render(f, rect),
             CurrentTask::FilterBar(filter_bar) => filter_bar.render(f, rect),
+            CurrentTask::LabelBar(label_bar) => label_bar.render(f, rect),
+            CurrentTask::QuickJumpBar(jump_bar) => jump_bar.render(f, rect),
             CurrentTask::Default(default_bar) => default_bar.render(f, rect),
             CurrentTask::Status(status_bar) => status_bar.render(f, rect),
         }
     }
 
     fn tick(&mut self) {
-        self.current_task.tick()
+        self.current_task.tick();
+
+        // Don't let a backlog of messages pile up behind a finished one
+        if let CurrentTask::Status(status_bar) = &self.current_task {
+            if !self.status_queue.is_empty() && status_bar.finished_for(QUEUED_STATUS_DURATION) {
+                self.show_next_status();
+            }
+        }
     }
 }
 
@@ -132,8 +144,14 @@ impl TaskManager {
         self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
-    pub fn search(&mut self, filter: &Option<Filter>) {
-        self.current_task = CurrentTask::FilterBar(FilterBar::new(self.ctx.clone(), filter));
+    pub fn search(&mut self, filter: &Option<Filter>, case_matching: CaseMatching) {
+        self.current_task =
+            CurrentTask::FilterBar(FilterBar::new(self.ctx.clone(), filter, case_matching));
+        self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
+    pub fn quick_jump(&mut self) {
+        self.current_task = CurrentTask::QuickJumpBar(QuickJumpBar::new(self.ctx.clone()));
         self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
@@ -149,6 +167,16 @@ impl TaskManager {
         self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
     }
 
+    pub fn export(&mut self, records: Vec<ExportRecord>) {
+        self.current_task = CurrentTask::ExportBar(ExportBar::new(self.ctx.clone(), records));
+        self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
+    pub fn label_torrents(&mut self, torrents: Vec<(Id, Vec<String>)>) {
+        self.current_task = CurrentTask::LabelBar(LabelBar::new(self.ctx.clone(), torrents));
+        self.ctx.send_update_action(UpdateAction::SwitchToInputMode);
+    }
+
     pub fn move_torrent(&mut self, torrent: &RustmissionTorrent) {
         self.current_task = CurrentTask::MoveBar(MoveBar::new(
             self.ctx.clone(),
@@ -159,26 +187,50 @@ impl TaskManager {
     }
 
     fn success_task(&mut self, task: StatusTask) {
-        self.current_task = CurrentTask::Status(StatusBar::new(
+        let status_bar = StatusBar::new(
             self.ctx.clone(),
             task,
             CurrentTaskState::Success(Instant::now()),
-        ))
+        );
+        self.show_status(status_bar);
     }
 
     fn pending_task(&mut self, task: StatusTask) {
-        if matches!(self.current_task, CurrentTask::Status(_)) {
-            return;
+        let state = ThrobberState::default();
+        let status_bar = StatusBar::new(self.ctx.clone(), task, CurrentTaskState::Loading(state));
+        self.show_status(status_bar);
+    }
+
+    // Shows the status right away if nothing else is, queues it otherwise.
+    fn show_status(&mut self, status_bar: StatusBar) {
+        if matches!(self.current_task, CurrentTask::Default(_)) {
+            self.current_task = CurrentTask::Status(status_bar);
+        } else {
+            self.status_queue.push_back(status_bar);
+        }
+    }
+
+    // The oldest task that's still waiting for its result
+    fn loading_status(&mut self) -> Option<&mut StatusBar> {
+        if let CurrentTask::Status(status_bar) = &mut self.current_task {
+            if status_bar.is_loading() {
+                return Some(status_bar);
+            }
         }
+        self.status_queue
+            .iter_mut()
+            .find(|status_bar| status_bar.is_loading())
+    }
 
-        let state = ThrobberState::default();
-        self.current_task = CurrentTask::Status(StatusBar::new(
-            self.ctx.clone(),
-            task,
-            CurrentTaskState::Loading(state),
-        ));
-        self.ctx
-            .send_update_action(UpdateAction::SwitchToNormalMode);
+    fn show_next_status(&mut self) {
+        self.current_task = match self.status_queue.pop_front() {
+            Some(mut status_bar) => {
+                status_bar.restart_timer();
+                CurrentTask::Status(status_bar)
+            }
+            None => CurrentTask::Default(DefaultBar::new()),
+        };
+        self.ctx.send_action(Action::Render);
     }
 
     fn cancel_task(&mut self) {
@@ -186,7 +238,7 @@ impl TaskManager {
             return;
         }
 
-        self.current_task = CurrentTask::Default(DefaultBar::new());
+        self.show_next_status();
         self.ctx
             .send_update_action(UpdateAction::SwitchToNormalMode);
     }
@@ -1,6 +1,8 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::prelude::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::Paragraph};
+use rm_config::{state::State, CONFIG};
 
+use super::torrent_file_picker::{PickerResponse, TorrentFilePicker};
 use crate::{
     transmission::TorrentAction,
     tui::{
@@ -17,37 +19,269 @@ pub struct AddMagnetBar {
     input_magnet_mgr: InputManager,
     input_location_mgr: InputManager,
     stage: Stage,
+    // Links from a multi-line paste, added along with whatever is typed
+    pasted: Vec<String>,
+    // Links that passed validation, and how many didn't
+    links: Vec<String>,
+    skipped: usize,
+    completion: Option<DirCompletion>,
+    error: Option<&'static str>,
     ctx: app::Ctx,
 }
 
+// Directories matching what was typed, cycled through with repeated Tab presses
+struct DirCompletion {
+    parent: String,
+    matches: Vec<String>,
+    current: usize,
+}
+
+impl DirCompletion {
+    fn candidate(&self) -> String {
+        format!("{}{}/", self.parent, self.matches[self.current])
+    }
+}
+
 enum Stage {
     AskMagnet,
+    PickFile(TorrentFilePicker),
     AskLocation,
 }
 
 impl AddMagnetBar {
     pub fn new(ctx: app::Ctx) -> Self {
+        let directory = State::load()
+            .last_add_dir
+            .unwrap_or_else(|| ctx.session_info.download_dir.clone());
+
         Self {
-            input_magnet_mgr: InputManager::new("Add (Magnet URL / Torrent path): ".to_string()),
-            input_location_mgr: InputManager::new_with_value(
-                "Directory: ".to_string(),
-                ctx.session_info.download_dir.clone(),
-            ),
+            input_magnet_mgr: InputManager::new(Self::magnet_prompt()),
+            input_location_mgr: InputManager::new_with_value(Self::location_prompt(), directory),
             stage: Stage::AskMagnet,
+            pasted: vec![],
+            links: vec![],
+            skipped: 0,
+            completion: None,
+            error: None,
             ctx,
         }
     }
 
+    fn magnet_prompt() -> String {
+        if CONFIG.general.beginner_mode {
+            "Add (Magnet URL / Torrent path, Ctrl-o browses files): ".to_string()
+        } else {
+            "Add (Magnet URL / Torrent path): ".to_string()
+        }
+    }
+
+    fn location_prompt() -> String {
+        if CONFIG.general.beginner_mode {
+            "Directory (Ctrl-r resets to default): ".to_string()
+        } else {
+            "Directory: ".to_string()
+        }
+    }
+
+    fn reset_location(&mut self) {
+        self.input_location_mgr = InputManager::new_with_value(
+            Self::location_prompt(),
+            self.ctx.session_info.download_dir.clone(),
+        );
+        self.ctx.send_action(Action::Render);
+    }
+
+    // Alt+1 picks the first of the configured shortcuts, Alt+2 the second and so on
+    fn pick_dir_shortcut(&mut self, digit: char) {
+        let Some(idx) = digit.to_digit(10).and_then(|n| n.checked_sub(1)) else {
+            return;
+        };
+        if let Some(shortcut) = CONFIG.connection.dir_shortcuts.get(idx as usize) {
+            self.completion = None;
+            self.input_location_mgr.set_text(shortcut.path.clone());
+            self.ctx.send_action(Action::Render);
+        }
+    }
+
+    fn dir_shortcuts_hint() -> Option<String> {
+        if CONFIG.connection.dir_shortcuts.is_empty() {
+            return None;
+        }
+
+        let hints: Vec<String> = CONFIG
+            .connection
+            .dir_shortcuts
+            .iter()
+            .take(9)
+            .enumerate()
+            .map(|(idx, shortcut)| format!("Alt-{} {}", idx + 1, shortcut.name))
+            .collect();
+        Some(format!("{} ", hints.join(" | ")))
+    }
+
+    fn complete_location(&mut self, backwards: bool) {
+        if let Some(completion) = &mut self.completion {
+            let matches_count = completion.matches.len();
+            completion.current = if backwards {
+                (completion.current + matches_count - 1) % matches_count
+            } else {
+                (completion.current + 1) % matches_count
+            };
+            self.input_location_mgr.set_text(completion.candidate());
+            self.ctx.send_action(Action::Render);
+            return;
+        }
+
+        let text = self.input_location_mgr.text();
+        let (parent, partial) = match text.rfind('/') {
+            Some(idx) => text.split_at(idx + 1),
+            None => ("", text.as_str()),
+        };
+
+        let matches = matching_dirs(parent, partial);
+        match matches.as_slice() {
+            [] => return,
+            [only_match] => {
+                self.input_location_mgr
+                    .set_text(format!("{parent}{only_match}/"));
+            }
+            _ => {
+                let common_prefix = common_prefix(&matches);
+                if common_prefix.len() > partial.len() {
+                    self.input_location_mgr
+                        .set_text(format!("{parent}{common_prefix}"));
+                } else {
+                    let completion = DirCompletion {
+                        parent: parent.to_string(),
+                        matches,
+                        current: 0,
+                    };
+                    self.input_location_mgr.set_text(completion.candidate());
+                    self.completion = Some(completion);
+                }
+            }
+        }
+        self.ctx.send_action(Action::Render);
+    }
+
     fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
         match self.stage {
             Stage::AskMagnet => self.handle_magnet_input(input),
+            Stage::PickFile(_) => self.handle_picker_input(input),
             Stage::AskLocation => self.handle_location_input(input),
         }
     }
 
+    fn handle_paste(&mut self, text: &str) {
+        match self.stage {
+            Stage::AskMagnet => {
+                let lines: Vec<&str> = text
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                if let [line] = lines.as_slice() {
+                    self.input_magnet_mgr.paste(line);
+                } else {
+                    self.pasted.extend(lines.into_iter().map(String::from));
+                }
+                self.error = None;
+            }
+            Stage::PickFile(_) => return,
+            Stage::AskLocation => {
+                self.completion = None;
+                self.input_location_mgr.paste(text.trim());
+            }
+        }
+        self.ctx.send_action(Action::Render);
+    }
+
+    // Keeps the valid links, skipping the rest.
+    // Only fails when there's nothing valid to add.
+    fn collect_links(&mut self) -> Result<(), &'static str> {
+        let typed = self.input_magnet_mgr.text().trim().to_string();
+        let mut candidates = self.pasted.clone();
+        if !typed.is_empty() || candidates.is_empty() {
+            candidates.push(typed);
+        }
+
+        let mut first_error = None;
+        self.links.clear();
+        self.skipped = 0;
+        for candidate in candidates {
+            match validate_magnet_input(&candidate) {
+                Ok(()) => self.links.push(candidate),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                    self.skipped += 1;
+                }
+            }
+        }
+
+        match first_error {
+            Some(error) if self.links.is_empty() => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn add_links(&self) {
+        let directory = self.input_location_mgr.text();
+        if let ([link], 0) = (self.links.as_slice(), self.skipped) {
+            let torrent_action = TorrentAction::Add(link.clone(), Some(directory));
+            self.ctx.send_torrent_action(torrent_action);
+            let task = StatusTask::new_add(link);
+            self.ctx.send_update_action(UpdateAction::TaskSet(task));
+            return;
+        }
+
+        for link in &self.links {
+            let torrent_action =
+                TorrentAction::AddQueued(link.clone(), Some(directory.clone()), None);
+            self.ctx.send_torrent_action(torrent_action);
+        }
+
+        let mut task = StatusTask::new_queue(format!("{} torrents", self.links.len()));
+        if self.skipped > 0 {
+            task = task.with_success_hint(format!("skipped {} invalid lines", self.skipped));
+        }
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(task));
+    }
+
+    fn handle_picker_input(&mut self, input: KeyEvent) -> ComponentAction {
+        let Stage::PickFile(picker) = &mut self.stage else {
+            return ComponentAction::Nothing;
+        };
+
+        match picker.handle_key(input) {
+            PickerResponse::Nothing => return ComponentAction::Nothing,
+            PickerResponse::Render => (),
+            PickerResponse::Picked(path) => {
+                self.input_magnet_mgr
+                    .set_text(path.to_string_lossy().into_owned());
+                match self.collect_links() {
+                    Ok(()) => self.stage = Stage::AskLocation,
+                    Err(error) => {
+                        self.error = Some(error);
+                        self.stage = Stage::AskMagnet;
+                    }
+                }
+            }
+            PickerResponse::Cancel => self.stage = Stage::AskMagnet,
+        }
+
+        self.ctx.send_action(Action::Render);
+        ComponentAction::Nothing
+    }
+
     fn handle_magnet_input(&mut self, input: KeyEvent) -> ComponentAction {
+        self.error = None;
+
         if input.code == KeyCode::Enter {
-            self.stage = Stage::AskLocation;
+            match self.collect_links() {
+                Ok(()) => self.stage = Stage::AskLocation,
+                Err(error) => self.error = Some(error),
+            }
             self.ctx.send_action(Action::Render);
             return ComponentAction::Nothing;
         }
@@ -56,6 +290,12 @@ impl AddMagnetBar {
             return ComponentAction::Quit;
         }
 
+        if input.code == KeyCode::Char('o') && input.modifiers == KeyModifiers::CONTROL {
+            self.stage = Stage::PickFile(TorrentFilePicker::new());
+            self.ctx.send_action(Action::Render);
+            return ComponentAction::Nothing;
+        }
+
         if self.input_magnet_mgr.handle_key(input).is_some() {
             self.ctx.send_action(Action::Render);
         }
@@ -64,19 +304,25 @@ impl AddMagnetBar {
     }
 
     fn handle_location_input(&mut self, input: KeyEvent) -> ComponentAction {
-        if input.code == KeyCode::Enter {
-            let torrent_action = TorrentAction::Add(
-                self.input_magnet_mgr.text(),
-                Some(self.input_location_mgr.text()),
-            );
-            self.ctx.send_torrent_action(torrent_action);
-
-            let task = StatusTask::new_add(self.input_magnet_mgr.text());
-            self.ctx.send_update_action(UpdateAction::TaskSet(task));
+        if matches!(input.code, KeyCode::Tab | KeyCode::BackTab) {
+            self.complete_location(input.code == KeyCode::BackTab);
+            return ComponentAction::Nothing;
+        }
+        self.completion = None;
 
+        if input.code == KeyCode::Enter {
+            self.add_links();
             ComponentAction::Quit
         } else if input.code == KeyCode::Esc {
             ComponentAction::Quit
+        } else if input.code == KeyCode::Char('r') && input.modifiers == KeyModifiers::CONTROL {
+            self.reset_location();
+            ComponentAction::Nothing
+        } else if let (KeyCode::Char(digit @ '1'..='9'), KeyModifiers::ALT) =
+            (input.code, input.modifiers)
+        {
+            self.pick_dir_shortcut(digit);
+            ComponentAction::Nothing
         } else if self.input_location_mgr.handle_key(input).is_some() {
             self.ctx.send_action(Action::Render);
             ComponentAction::Nothing
@@ -87,18 +333,126 @@ impl AddMagnetBar {
 }
 
 impl Component for AddMagnetBar {
-    #[must_use]
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                self.handle_paste(&text);
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
 
     fn render(&mut self, f: &mut Frame, rect: Rect) {
-        match self.stage {
-            Stage::AskMagnet => self.input_magnet_mgr.render(f, rect),
-            Stage::AskLocation => self.input_location_mgr.render(f, rect),
+        match &mut self.stage {
+            Stage::AskMagnet => {
+                self.input_magnet_mgr.render(f, rect);
+                if let Some(error) = self.error {
+                    let error = Paragraph::new(format!("{error} "))
+                        .fg(CONFIG.theme.error)
+                        .right_aligned();
+                    f.render_widget(error, rect);
+                } else if !self.pasted.is_empty() {
+                    let pasted = Paragraph::new(format!("{} links pasted ", self.pasted.len()))
+                        .fg(CONFIG.general.accent_color)
+                        .right_aligned();
+                    f.render_widget(pasted, rect);
+                }
+            }
+            Stage::PickFile(picker) => {
+                self.input_magnet_mgr.render(f, rect);
+                picker.render(f);
+            }
+            Stage::AskLocation => {
+                let Some(hint) = Self::dir_shortcuts_hint() else {
+                    self.input_location_mgr.render(f, rect);
+                    return;
+                };
+
+                // The input gives up room for the hint, but keeps at least half of the bar
+                let hint_width = u16::try_from(Span::raw(&hint).width()).unwrap_or(u16::MAX);
+                let [input_rect, hint_rect] = Layout::horizontal([
+                    Constraint::Min(rect.width / 2),
+                    Constraint::Max(hint_width),
+                ])
+                .areas(rect);
+                self.input_location_mgr.render(f, input_rect);
+                let hint = Paragraph::new(hint)
+                    .fg(CONFIG.general.accent_color)
+                    .right_aligned();
+                f.render_widget(hint, hint_rect);
+            }
         }
     }
 }
+
+// Names of directories inside `parent` that start with `partial`.
+// Hidden ones are only offered when `partial` starts with a dot.
+fn matching_dirs(parent: &str, partial: &str) -> Vec<String> {
+    let dir = if parent.is_empty() { "." } else { parent };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut matches: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(partial))
+        .filter(|name| !name.starts_with('.') || partial.starts_with('.'))
+        .collect();
+    matches.sort();
+    matches
+}
+
+fn common_prefix(strings: &[String]) -> String {
+    let Some((first, rest)) = strings.split_first() else {
+        return String::new();
+    };
+
+    let mut prefix_len = first.len();
+    for string in rest {
+        prefix_len = first
+            .char_indices()
+            .zip(string.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((idx, c), _)| idx + c.len_utf8())
+            .min(prefix_len);
+    }
+
+    first[..prefix_len].to_string()
+}
+
+fn validate_magnet_input(input: &str) -> Result<(), &'static str> {
+    if input.is_empty() {
+        return Err("Nothing to add");
+    }
+
+    if input.starts_with("magnet:?")
+        || input.starts_with("http://")
+        || input.starts_with("https://")
+        || input.starts_with("www")
+    {
+        return Ok(());
+    }
+
+    if input.starts_with("magnet:") {
+        return Err("Malformed magnet link");
+    }
+
+    // A path on a remote server can't be checked from here
+    if is_transmission_local() && !std::path::Path::new(input).is_file() {
+        return Err("No such torrent file");
+    }
+
+    Ok(())
+}
+
+fn is_transmission_local() -> bool {
+    matches!(
+        CONFIG.connection.url.host_str(),
+        Some("localhost" | "127.0.0.1" | "[::1]")
+    )
+}
@@ -9,17 +9,24 @@ impl DefaultBar {
     pub const fn new() -> Self {
         Self {}
     }
+
+    // Shown at the left of the bottom bar in beginner mode
+    pub fn help_hint() -> Option<String> {
+        if !CONFIG.general.beginner_mode {
+            return None;
+        }
+
+        CONFIG
+            .keybindings
+            .get_keys_for_action(rm_shared::action::Action::ShowHelp)
+            .map(|keys| format!("󰘥 {keys} - help"))
+    }
 }
 
 impl Component for DefaultBar {
     fn render(&mut self, f: &mut ratatui::Frame<'_>, rect: Rect) {
-        if CONFIG.general.beginner_mode {
-            if let Some(keys) = CONFIG
-                .keybindings
-                .get_keys_for_action(rm_shared::action::Action::ShowHelp)
-            {
-                f.render_widget(format!("󰘥 {keys} - help"), rect)
-            }
+        if let Some(hint) = Self::help_hint() {
+            f.render_widget(hint, rect)
         }
     }
 }
@@ -1,5 +1,6 @@
 use crossterm::event::KeyCode;
 use ratatui::prelude::*;
+use rm_config::CONFIG;
 use transmission_rpc::types::Id;
 
 use crate::transmission::TorrentAction;
@@ -58,7 +59,12 @@ impl DeleteBar {
                 .send_torrent_action(TorrentAction::DelWithoutFiles(torrents_to_delete)),
         }
 
-        let task = StatusTask::new_del(self.torrents_to_delete[0].name.clone());
+        let mut task = StatusTask::new_del(self.torrents_to_delete[0].name.clone());
+        if let Mode::WithoutFiles = self.mode {
+            if let Some(key) = CONFIG.keybindings.get_keys_for_action(Action::UndoRemoval) {
+                task = task.with_success_hint(format!("press {key} to undo"));
+            }
+        }
         self.ctx.send_update_action(UpdateAction::TaskSet(task));
     }
 }
@@ -85,6 +91,12 @@ impl Component for DeleteBar {
 
                 ComponentAction::Nothing
             }
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text) {
+                    self.ctx.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
@@ -0,0 +1,155 @@
+use std::{fs, io, path::Path};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use rm_shared::{
+    action::{Action, ErrorMessage, UpdateAction},
+    status_task::StatusTask,
+};
+use serde::Serialize;
+
+use crate::tui::{
+    app,
+    components::{Component, ComponentAction, InputManager},
+    tabs::torrents::rustmission_torrent::RustmissionTorrent,
+};
+
+#[derive(Serialize)]
+pub struct ExportRecord {
+    name: String,
+    size: String,
+    progress: String,
+    ratio: String,
+    status: String,
+    download_speed: String,
+    upload_speed: String,
+}
+
+impl From<&RustmissionTorrent> for ExportRecord {
+    fn from(torrent: &RustmissionTorrent) -> Self {
+        // Empty cells in the table mean finished / idle
+        let or_default = |value: &str, default: &str| {
+            if value.is_empty() {
+                default.to_string()
+            } else {
+                value.to_string()
+            }
+        };
+
+        Self {
+            name: torrent.torrent_name.clone(),
+            size: torrent.size_when_done.clone(),
+            progress: or_default(&torrent.progress, "100.00%"),
+            ratio: torrent.upload_ratio.clone(),
+            status: format!("{:?}", torrent.status()),
+            download_speed: or_default(&torrent.download_speed, "0"),
+            upload_speed: or_default(&torrent.upload_speed, "0"),
+        }
+    }
+}
+
+pub struct ExportBar {
+    records: Vec<ExportRecord>,
+    ctx: app::Ctx,
+    input_mgr: InputManager,
+}
+
+impl ExportBar {
+    pub fn new(ctx: app::Ctx, records: Vec<ExportRecord>) -> Self {
+        let prompt = "Export to (.csv or .json): ".to_string();
+
+        Self {
+            records,
+            input_mgr: InputManager::new_with_value(prompt, "torrents.csv".to_string()),
+            ctx,
+        }
+    }
+
+    fn export(&self, path: &str) {
+        let content = if path.ends_with(".json") {
+            serde_json::to_string_pretty(&self.records).map_err(io::Error::from)
+        } else {
+            Ok(to_csv(&self.records))
+        };
+
+        match content.and_then(|content| fs::write(Path::new(path), content)) {
+            Ok(()) => {
+                let task = StatusTask::new_export(path);
+                self.ctx
+                    .send_update_action(UpdateAction::TaskSetSuccess(task));
+            }
+            Err(err) => {
+                let desc = format!("Couldn't write torrents to \"{path}\"");
+                let err_message =
+                    ErrorMessage::new("Failed to export torrents", desc, Box::new(err));
+                self.ctx
+                    .send_update_action(UpdateAction::Error(Box::new(err_message)));
+            }
+        }
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
+        if input.code == KeyCode::Enter {
+            let path = self.input_mgr.text();
+            if !path.trim().is_empty() {
+                self.export(path.trim());
+            }
+            ComponentAction::Quit
+        } else if input.code == KeyCode::Esc {
+            ComponentAction::Quit
+        } else if self.input_mgr.handle_key(input).is_some() {
+            self.ctx.send_action(Action::Render);
+            ComponentAction::Nothing
+        } else {
+            ComponentAction::Nothing
+        }
+    }
+}
+
+impl Component for ExportBar {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text) {
+                    self.ctx.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        self.input_mgr.render(f, rect)
+    }
+}
+
+fn to_csv(records: &[ExportRecord]) -> String {
+    let mut csv = String::from("name,size,progress,ratio,status,download_speed,upload_speed\n");
+
+    for record in records {
+        let fields = [
+            &record.name,
+            &record.size,
+            &record.progress,
+            &record.ratio,
+            &record.status,
+            &record.download_speed,
+            &record.upload_speed,
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
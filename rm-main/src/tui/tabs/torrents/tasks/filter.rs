@@ -1,7 +1,11 @@
 use crossterm::event::KeyCode;
-use ratatui::prelude::*;
+use ratatui::{prelude::*, widgets::Paragraph};
+use rm_config::{state::State, CONFIG};
 
-use rm_shared::action::{Action, UpdateAction};
+use rm_shared::{
+    action::{Action, UpdateAction},
+    utils::CaseMatching,
+};
 
 use crate::tui::{
     app,
@@ -12,10 +16,19 @@ use crate::tui::{
 pub struct FilterBar {
     ctx: app::Ctx,
     input: InputManager,
+    case_matching: CaseMatching,
+    history: Vec<String>,
+    // Which history entry is shown, and what was typed before browsing it
+    history_idx: Option<usize>,
+    draft: String,
 }
 
 impl FilterBar {
-    pub fn new(ctx: app::Ctx, current_filter: &Option<Filter>) -> Self {
+    pub fn new(
+        ctx: app::Ctx,
+        current_filter: &Option<Filter>,
+        case_matching: CaseMatching,
+    ) -> Self {
         let filter = {
             if let Some(current_filter) = current_filter {
                 current_filter.pattern.clone()
@@ -25,7 +38,49 @@ impl FilterBar {
         };
 
         let input = InputManager::new_with_value("Search: ".to_string(), filter);
-        Self { ctx, input }
+        Self {
+            ctx,
+            input,
+            case_matching,
+            history: State::load().filter_history,
+            history_idx: None,
+            draft: String::new(),
+        }
+    }
+
+    fn history_previous(&mut self) {
+        let idx = match self.history_idx {
+            _ if self.history.is_empty() => return,
+            None => {
+                self.draft = self.input.text();
+                self.history.len() - 1
+            }
+            Some(idx) => idx.saturating_sub(1),
+        };
+
+        self.history_idx = Some(idx);
+        self.input.set_text(self.history[idx].clone());
+        self.apply();
+    }
+
+    fn history_next(&mut self) {
+        let Some(idx) = self.history_idx else {
+            return;
+        };
+
+        if idx + 1 < self.history.len() {
+            self.history_idx = Some(idx + 1);
+            self.input.set_text(self.history[idx + 1].clone());
+        } else {
+            self.history_idx = None;
+            self.input.set_text(std::mem::take(&mut self.draft));
+        }
+        self.apply();
+    }
+
+    fn apply(&self) {
+        self.ctx
+            .send_update_action(UpdateAction::SearchFilterApply(self.input.text()));
     }
 }
 
@@ -34,23 +89,55 @@ impl Component for FilterBar {
         match action {
             Action::Input(input) => {
                 if matches!(input.code, KeyCode::Enter | KeyCode::Esc) {
-                    if self.input.text().is_empty() {
+                    let text = self.input.text();
+                    if text.is_empty() {
                         self.ctx.send_update_action(UpdateAction::SearchFilterClear);
+                    } else if input.code == KeyCode::Enter {
+                        State::remember_filter(text);
                     }
                     ComponentAction::Quit
-                } else if self.input.handle_key(input).is_some() {
+                } else if input.code == KeyCode::Up {
+                    self.history_previous();
+                    ComponentAction::Nothing
+                } else if input.code == KeyCode::Down {
+                    self.history_next();
+                    ComponentAction::Nothing
+                } else if input.code == KeyCode::Tab {
+                    self.case_matching = self.case_matching.next();
                     self.ctx
-                        .send_update_action(UpdateAction::SearchFilterApply(self.input.text()));
+                        .send_update_action(UpdateAction::SearchFilterCaseMatching(
+                            self.case_matching,
+                        ));
+                    if !self.input.text().is_empty() {
+                        self.apply();
+                    }
+                    ComponentAction::Nothing
+                } else if self.input.handle_key(input).is_some() {
+                    self.history_idx = None;
+                    self.apply();
                     ComponentAction::Nothing
                 } else {
                     ComponentAction::Nothing
                 }
             }
+            Action::Paste(text) => {
+                if self.input.paste(&text) {
+                    self.history_idx = None;
+                    self.apply();
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
 
     fn render(&mut self, f: &mut Frame, rect: Rect) {
         self.input.render(f, rect);
+
+        let mut indicator = format!("[{}] ", self.case_matching.desc());
+        if CONFIG.general.beginner_mode {
+            indicator = format!("↑/↓ - history | Tab - change case matching {indicator}");
+        }
+        f.render_widget(Paragraph::new(indicator).dark_gray().right_aligned(), rect);
     }
 }
@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use rm_shared::{
+    action::{Action, UpdateAction},
+    status_task::StatusTask,
+};
+use transmission_rpc::types::Id;
+
+use crate::{
+    transmission::TorrentAction,
+    tui::{
+        app,
+        components::{Component, ComponentAction, InputManager},
+        tabs::torrents::torrents_count_str,
+    },
+};
+
+pub struct LabelBar {
+    // Torrent IDs with labels they already have
+    torrents_to_label: Vec<(Id, Vec<String>)>,
+    ctx: app::Ctx,
+    input_mgr: InputManager,
+}
+
+impl LabelBar {
+    pub fn new(ctx: app::Ctx, torrents_to_label: Vec<(Id, Vec<String>)>) -> Self {
+        let prompt = format!(
+            "Label {} with: ",
+            torrents_count_str(torrents_to_label.len())
+        );
+
+        Self {
+            torrents_to_label,
+            input_mgr: InputManager::new(prompt),
+            ctx,
+        }
+    }
+
+    fn label(&self, label: String) {
+        for (id, labels) in &self.torrents_to_label {
+            if labels.contains(&label) {
+                continue;
+            }
+
+            let mut labels = labels.clone();
+            labels.push(label.clone());
+            self.ctx.send_torrent_action(TorrentAction::SetLabels {
+                id: id.clone(),
+                labels,
+            });
+        }
+
+        let what = format!(
+            "{} with \"{label}\"",
+            torrents_count_str(self.torrents_to_label.len())
+        );
+        self.ctx
+            .send_update_action(UpdateAction::TaskSetSuccess(StatusTask::new_label(what)));
+    }
+
+    fn handle_input(&mut self, input: KeyEvent) -> ComponentAction {
+        if input.code == KeyCode::Enter {
+            let label = self.input_mgr.text().trim().to_string();
+            if !label.is_empty() {
+                self.label(label);
+            }
+            ComponentAction::Quit
+        } else if input.code == KeyCode::Esc {
+            ComponentAction::Quit
+        } else if self.input_mgr.handle_key(input).is_some() {
+            self.ctx.send_action(Action::Render);
+            ComponentAction::Nothing
+        } else {
+            ComponentAction::Nothing
+        }
+    }
+}
+
+impl Component for LabelBar {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text) {
+                    self.ctx.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        self.input_mgr.render(f, rect)
+    }
+}
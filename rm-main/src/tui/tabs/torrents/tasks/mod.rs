@@ -1,6 +1,10 @@
 pub mod add_magnet;
 pub mod default;
 pub mod delete_torrent;
+pub mod export;
 pub mod filter;
+pub mod label;
 pub mod move_torrent;
+pub mod quick_jump;
 pub mod status;
+pub mod torrent_file_picker;
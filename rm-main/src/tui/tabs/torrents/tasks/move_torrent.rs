@@ -58,6 +58,12 @@ impl Component for MoveBar {
     fn handle_actions(&mut self, action: Action) -> ComponentAction {
         match action {
             Action::Input(input) => self.handle_input(input),
+            Action::Paste(text) => {
+                if self.input_mgr.paste(&text) {
+                    self.ctx.send_action(Action::Render);
+                }
+                ComponentAction::Nothing
+            }
             _ => ComponentAction::Nothing,
         }
     }
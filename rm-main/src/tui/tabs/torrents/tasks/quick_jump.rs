@@ -0,0 +1,51 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+
+use rm_shared::action::{Action, UpdateAction};
+
+use crate::tui::{
+    app,
+    components::{Component, ComponentAction, InputManager},
+};
+
+pub struct QuickJumpBar {
+    ctx: app::Ctx,
+    input: InputManager,
+}
+
+impl QuickJumpBar {
+    pub fn new(ctx: app::Ctx) -> Self {
+        let input = InputManager::new("Jump to: ".to_string());
+        Self { ctx, input }
+    }
+}
+
+impl Component for QuickJumpBar {
+    fn handle_actions(&mut self, action: Action) -> ComponentAction {
+        match action {
+            Action::Input(input) => {
+                if matches!(input.code, KeyCode::Enter | KeyCode::Esc) {
+                    ComponentAction::Quit
+                } else if self.input.handle_key(input).is_some() {
+                    self.ctx
+                        .send_update_action(UpdateAction::QuickJump(self.input.text()));
+                    ComponentAction::Nothing
+                } else {
+                    ComponentAction::Nothing
+                }
+            }
+            Action::Paste(text) => {
+                if self.input.paste(&text) {
+                    self.ctx
+                        .send_update_action(UpdateAction::QuickJump(self.input.text()));
+                }
+                ComponentAction::Nothing
+            }
+            _ => ComponentAction::Nothing,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, rect: Rect) {
+        self.input.render(f, rect);
+    }
+}
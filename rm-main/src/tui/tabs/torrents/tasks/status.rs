@@ -37,6 +37,30 @@ impl StatusBar {
     pub fn set_success(&mut self) {
         self.task_status = CurrentTaskState::Success(Instant::now());
     }
+
+    pub const fn is_loading(&self) -> bool {
+        matches!(self.task_status, CurrentTaskState::Loading(_))
+    }
+
+    // Whether it's done and has been on screen for at least `duration`
+    pub fn finished_for(&self, duration: time::Duration) -> bool {
+        match &self.task_status {
+            CurrentTaskState::Loading(_) => false,
+            CurrentTaskState::Success(start) | CurrentTaskState::Failure(start) => {
+                start.elapsed() >= duration
+            }
+        }
+    }
+
+    // Makes a message that waited in the queue stay for its full duration once shown
+    pub fn restart_timer(&mut self) {
+        match &mut self.task_status {
+            CurrentTaskState::Loading(_) => (),
+            CurrentTaskState::Success(start) | CurrentTaskState::Failure(start) => {
+                *start = Instant::now();
+            }
+        }
+    }
 }
 
 impl Component for StatusBar {
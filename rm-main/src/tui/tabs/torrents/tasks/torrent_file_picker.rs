@@ -0,0 +1,168 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::*,
+    style::Styled,
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Clear, List, ListItem, ListState,
+    },
+};
+use rm_config::CONFIG;
+
+use crate::tui::main_window::centered_rect;
+
+// A minimal browser listing only directories and .torrent files
+pub struct TorrentFilePicker {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    state: ListState,
+}
+
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+pub enum PickerResponse {
+    Nothing,
+    Render,
+    Picked(PathBuf),
+    Cancel,
+}
+
+impl TorrentFilePicker {
+    pub fn new() -> Self {
+        let dir = CONFIG
+            .torrents_tab
+            .torrent_files_dir
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let mut picker = Self {
+            dir: PathBuf::new(),
+            entries: vec![],
+            state: ListState::default(),
+        };
+        picker.open_dir(dir);
+        picker
+    }
+
+    fn open_dir(&mut self, dir: PathBuf) {
+        self.entries = list_dir(&dir);
+        self.dir = dir;
+        self.state.select((!self.entries.is_empty()).then_some(0));
+    }
+
+    fn open_parent(&mut self) {
+        if let Some(parent) = self.dir.parent() {
+            let parent = parent.to_path_buf();
+            self.open_dir(parent);
+        }
+    }
+
+    fn select_offset(&mut self, forward: bool) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+
+        let selected = self.state.selected().unwrap_or_default();
+        let next = if forward {
+            (selected + 1) % len
+        } else {
+            (selected + len - 1) % len
+        };
+        self.state.select(Some(next));
+    }
+
+    pub fn handle_key(&mut self, input: KeyEvent) -> PickerResponse {
+        match input.code {
+            KeyCode::Esc => return PickerResponse::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => self.select_offset(true),
+            KeyCode::Char('k') | KeyCode::Up => self.select_offset(false),
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => self.open_parent(),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                let Some(entry) = self.state.selected().and_then(|idx| self.entries.get(idx))
+                else {
+                    return PickerResponse::Nothing;
+                };
+
+                let path = self.dir.join(&entry.name);
+                if entry.is_dir {
+                    self.open_dir(path);
+                } else {
+                    return PickerResponse::Picked(path);
+                }
+            }
+            _ => return PickerResponse::Nothing,
+        }
+
+        PickerResponse::Render
+    }
+
+    pub fn render(&mut self, f: &mut Frame) {
+        let popup_rect = centered_rect(f.size(), 60, 60);
+        let block_rect = popup_rect.inner(Margin::new(1, 1));
+
+        let title_style = Style::default().fg(CONFIG.general.accent_color);
+        let mut block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(Title::from(
+                format!(" {} ", self.dir.display()).set_style(title_style),
+            ));
+        if CONFIG.general.beginner_mode {
+            block = block.title(
+                Title::from(" Enter - open/add | Backspace - parent directory | Esc - back ")
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            );
+        }
+
+        let items: Vec<ListItem> = if self.entries.is_empty() {
+            vec![ListItem::new("No directories or .torrent files here").dark_gray()]
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| {
+                    if entry.is_dir {
+                        ListItem::new(format!("{}/", entry.name)).fg(CONFIG.general.accent_color)
+                    } else {
+                        ListItem::new(entry.name.as_str())
+                    }
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(CONFIG.theme.selected_row_style());
+
+        f.render_widget(Clear, popup_rect);
+        f.render_stateful_widget(list, block_rect, &mut self.state);
+    }
+}
+
+// Visible directories first, then .torrent files, both sorted by name
+fn list_dir(dir: &Path) -> Vec<Entry> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut entries: Vec<Entry> = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let is_dir = entry.path().is_dir();
+            let is_torrent = Path::new(&name)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("torrent"));
+            (!name.starts_with('.') && (is_dir || is_torrent)).then_some(Entry { name, is_dir })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
@@ -0,0 +1,36 @@
+use std::{collections::HashSet, fs, sync::Once};
+
+use transmission_rpc::types::Torrent;
+
+use super::rustmission_torrent::RustmissionTorrent;
+
+// Points the config and the state at a fresh directory with the default config,
+// so that tests neither depend on nor touch the user's files.
+pub fn sandbox() {
+    static SANDBOX: Once = Once::new();
+    SANDBOX.call_once(|| {
+        let dir = std::env::temp_dir().join(format!("rustmission-tests-{}", std::process::id()));
+        let config_dir = dir.join("config").join("rustmission");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config.toml"),
+            include_str!("../../../../../rm-config/defaults/config.toml"),
+        )
+        .unwrap();
+
+        std::env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+        std::env::set_var("XDG_STATE_HOME", dir.join("state"));
+    });
+}
+
+pub fn torrent(id: i64, name: &str, size: i64) -> RustmissionTorrent {
+    sandbox();
+    let torrent: Torrent = serde_json::from_value(serde_json::json!({
+        "id": id,
+        "hashString": format!("{id:040x}"),
+        "name": name,
+        "sizeWhenDone": size,
+    }))
+    .unwrap();
+    RustmissionTorrent::new(torrent, &HashSet::new())
+}
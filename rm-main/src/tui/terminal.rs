@@ -3,11 +3,15 @@ use std::{io, time::Duration};
 use anyhow::Result;
 use crossterm::{
     cursor,
-    event::{Event, KeyEventKind},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyEventKind,
+    },
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::{FutureExt, StreamExt};
 use ratatui::{backend::CrosstermBackend as Backend, Terminal};
+use rm_config::CONFIG;
 use tokio::{
     sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
@@ -42,12 +46,12 @@ impl Tui {
         event_tx: &UnboundedSender<Event>,
     ) -> Result<()> {
         match event {
-            Some(Ok(Event::Key(key))) => {
-                if key.kind == KeyEventKind::Press {
-                    event_tx.send(Event::Key(key)).unwrap();
-                }
+            Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                event_tx.send(Event::Key(key)).unwrap();
             }
+            Some(Ok(Event::Mouse(mouse))) => event_tx.send(Event::Mouse(mouse)).unwrap(),
             Some(Ok(Event::Resize(x, y))) => event_tx.send(Event::Resize(x, y)).unwrap(),
+            Some(Ok(Event::Paste(text))) => event_tx.send(Event::Paste(text)).unwrap(),
             Some(Err(e)) => Err(e)?,
             _ => (),
         }
@@ -56,7 +60,15 @@ impl Tui {
 
     pub(crate) fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(std::io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        crossterm::execute!(
+            std::io::stdout(),
+            EnterAlternateScreen,
+            EnableBracketedPaste,
+            cursor::Hide
+        )?;
+        if CONFIG.general.mouse {
+            crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+        }
         self.start()?;
         Ok(())
     }
@@ -95,7 +107,15 @@ impl Tui {
         }
         if crossterm::terminal::is_raw_mode_enabled()? {
             self.terminal.flush()?;
-            crossterm::execute!(std::io::stdout(), LeaveAlternateScreen, cursor::Show)?;
+            if CONFIG.general.mouse {
+                crossterm::execute!(std::io::stdout(), DisableMouseCapture)?;
+            }
+            crossterm::execute!(
+                std::io::stdout(),
+                DisableBracketedPaste,
+                LeaveAlternateScreen,
+                cursor::Show
+            )?;
             crossterm::terminal::disable_raw_mode()?;
         }
         Ok(())
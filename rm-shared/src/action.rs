@@ -1,10 +1,17 @@
-use std::{error::Error, sync::Arc};
+use std::{error::Error, sync::Arc, time::Instant};
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use magnetease::{MagneteaseError, MagneteaseResult};
-use transmission_rpc::types::{FreeSpace, SessionStats, Torrent};
+use transmission_rpc::types::{FreeSpace, SessionStats, Torrent, TorrentGetField};
 
-use crate::status_task::StatusTask;
+use crate::{
+    header::Header,
+    session::{
+        AltSpeedSchedule, EffectiveLimits, SessionInfo, SessionSettings, TorrentUploadLimit,
+    },
+    status_task::StatusTask,
+    utils::CaseMatching,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -29,14 +36,42 @@ pub enum Action {
     ChangeTab(u8),
     XdgOpen,
     Input(KeyEvent),
+    Paste(String),
+    Mouse(MouseEvent),
     // Torrents Tab
     ShowStats,
     ShowFiles,
+    ShowDetails,
     Pause,
+    StartAll,
+    StartNow,
+    StartNowAll,
+    ToggleUpload,
+    PauseAll,
     DeleteWithoutFiles,
     DeleteWithFiles,
     AddMagnet,
     MoveTorrent,
+    QuickJump,
+    OpenDownloadDir,
+    ShowSessionSettings,
+    ShowSessionInfo,
+    ShowColumns,
+    ShowTrackerGroups,
+    ShowAltSpeedSchedule,
+    CopyHash,
+    ToggleTotals,
+    ResetView,
+    ToggleRowLayout,
+    CycleLabelFilter,
+    CyclePriority,
+    SortNextColumn,
+    ReverseSort,
+    NextSortPreset,
+    UndoRemoval,
+    VerifyAll,
+    LabelAll,
+    Export,
     // Search Tab
     ShowProvidersInfo,
 }
@@ -46,6 +81,8 @@ pub enum UpdateAction {
     SwitchToInputMode,
     SwitchToNormalMode,
     Error(Box<ErrorMessage>),
+    ConnectionLost,
+    ConnectionRestored,
     // Torrents Tab
     TaskClear,
     TaskSuccess,
@@ -54,10 +91,29 @@ pub enum UpdateAction {
     TaskSetSuccess(StatusTask),
     SessionStats(Arc<SessionStats>),
     FreeSpace(Arc<FreeSpace>),
-    UpdateTorrents(Vec<Torrent>),
+    // Torrents, along with the fields that were asked for
+    UpdateTorrents(Vec<Torrent>, Vec<TorrentGetField>),
+    // Upload limits of all the torrents, for telling which ones have uploading paused,
+    // along with when they were asked for
+    UploadLimits(Vec<TorrentUploadLimit>, Instant),
+    // Torrents should be fetched again right away, as what's shown might be wrong
+    RefreshTorrents,
     UpdateCurrentTorrent(Box<Torrent>),
+    // Details of the selected torrent, fetched before they're asked for
+    TorrentDetailsPrefetched(Box<Torrent>),
+    // Torrents that were just removed without their files, kept so the removal can be undone
+    TorrentsRemoved(Vec<Torrent>),
+    // The torrent that was being added turned out to be already there
+    TorrentDuplicate(Box<Torrent>),
+    SessionSettings(Box<SessionSettings>),
+    SessionInfo(Box<SessionInfo>),
+    AltSpeedSchedule(Box<AltSpeedSchedule>),
+    EffectiveLimits(EffectiveLimits),
+    SetColumns(Vec<Header>),
     SearchFilterApply(String),
     SearchFilterClear,
+    SearchFilterCaseMatching(CaseMatching),
+    QuickJump(String),
     // Search Tab
     SearchStarted,
     ProviderResult(MagneteaseResult),
@@ -16,11 +16,35 @@ pub enum Header {
     Id,
     ActivityDate,
     AddedDate,
+    DoneDate,
     PeersConnected,
+    PeersSendingToUs,
+    PeersGettingFromUs,
     SmallStatus,
 }
 
 impl Header {
+    pub const ALL: [Self; 18] = [
+        Self::Name,
+        Self::SizeWhenDone,
+        Self::Progress,
+        Self::Eta,
+        Self::DownloadRate,
+        Self::UploadRate,
+        Self::DownloadDir,
+        Self::Padding,
+        Self::UploadRatio,
+        Self::UploadedEver,
+        Self::Id,
+        Self::ActivityDate,
+        Self::AddedDate,
+        Self::DoneDate,
+        Self::PeersConnected,
+        Self::PeersSendingToUs,
+        Self::PeersGettingFromUs,
+        Self::SmallStatus,
+    ];
+
     pub fn default_constraint(&self) -> Constraint {
         match self {
             Self::Name => Constraint::Max(70),
@@ -36,11 +60,31 @@ impl Header {
             Self::Id => Constraint::Length(4),
             Self::ActivityDate => Constraint::Length(14),
             Self::AddedDate => Constraint::Length(12),
+            Self::DoneDate => Constraint::Length(12),
             Self::PeersConnected => Constraint::Length(6),
+            Self::PeersSendingToUs => Constraint::Length(6),
+            Self::PeersGettingFromUs => Constraint::Length(8),
             Self::SmallStatus => Constraint::Length(1),
         }
     }
 
+    // Width of a column that doesn't stretch with the terminal
+    pub fn fixed_width(&self) -> Option<u16> {
+        match self.default_constraint() {
+            Constraint::Length(width) => Some(width),
+            _ => None,
+        }
+    }
+
+    // Like header_name, but also for the columns without a header
+    pub fn description(&self) -> &'static str {
+        match *self {
+            Self::Padding => "Padding",
+            Self::SmallStatus => "Status icon",
+            _ => self.header_name(),
+        }
+    }
+
     pub fn header_name(&self) -> &'static str {
         match *self {
             Self::Name => "Name",
@@ -56,7 +100,10 @@ impl Header {
             Self::Id => "Id",
             Self::ActivityDate => "Last active",
             Self::AddedDate => "Added",
+            Self::DoneDate => "Completed",
             Self::PeersConnected => "Peers",
+            Self::PeersSendingToUs => "Seeds",
+            Self::PeersGettingFromUs => "Leechers",
             Self::SmallStatus => "",
         }
     }
@@ -1,4 +1,6 @@
 pub mod action;
 pub mod header;
+pub mod session;
+pub mod sort;
 pub mod status_task;
 pub mod utils;
@@ -0,0 +1,220 @@
+use serde::Deserialize;
+
+/// Session values that transmission-rpc's `SessionGet` doesn't expose.
+/// Speed limits are in KB/s, as reported by Transmission.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct SessionSettings {
+    pub speed_limit_down: i32,
+    pub speed_limit_down_enabled: bool,
+    pub speed_limit_up: i32,
+    pub speed_limit_up_enabled: bool,
+    pub peer_limit_global: i32,
+    pub peer_limit_per_torrent: i32,
+    pub download_queue_size: i32,
+    pub seed_queue_size: i32,
+    pub pex_enabled: bool,
+    pub dht_enabled: bool,
+    pub lpd_enabled: bool,
+}
+
+impl SessionSettings {
+    pub const FIELDS: &'static [&'static str] = &[
+        "speed-limit-down",
+        "speed-limit-down-enabled",
+        "speed-limit-up",
+        "speed-limit-up-enabled",
+        "peer-limit-global",
+        "peer-limit-per-torrent",
+        "download-queue-size",
+        "seed-queue-size",
+        "pex-enabled",
+        "dht-enabled",
+        "lpd-enabled",
+    ];
+}
+
+/// Details about the daemon itself. Older Transmission versions lack some
+/// of them, hence everything is optional.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct SessionInfo {
+    pub version: Option<String>,
+    pub rpc_version: Option<i32>,
+    pub config_dir: Option<String>,
+    pub dht_enabled: Option<bool>,
+    pub pex_enabled: Option<bool>,
+    pub port_forwarding_enabled: Option<bool>,
+}
+
+impl SessionInfo {
+    pub const FIELDS: &'static [&'static str] = &[
+        "version",
+        "rpc-version",
+        "config-dir",
+        "dht-enabled",
+        "pex-enabled",
+        "port-forwarding-enabled",
+    ];
+}
+
+/// Daily schedule of the alternative ("turtle") speed limits.
+/// Times are minutes after midnight.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct AltSpeedSchedule {
+    pub alt_speed_time_enabled: bool,
+    pub alt_speed_time_begin: i32,
+    pub alt_speed_time_end: i32,
+    // Bitmask of days, Sunday being 1 and Saturday 64
+    pub alt_speed_time_day: i32,
+}
+
+impl AltSpeedSchedule {
+    pub const FIELDS: &'static [&'static str] = &[
+        "alt-speed-time-enabled",
+        "alt-speed-time-begin",
+        "alt-speed-time-end",
+        "alt-speed-time-day",
+    ];
+}
+
+/// Session-wide speed limits, in KB/s. The alternative ("turtle") limits
+/// replace the regular ones while they're enabled.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct SessionSpeedLimits {
+    pub speed_limit_down: i32,
+    pub speed_limit_down_enabled: bool,
+    pub speed_limit_up: i32,
+    pub speed_limit_up_enabled: bool,
+    pub alt_speed_enabled: bool,
+    pub alt_speed_down: i32,
+    pub alt_speed_up: i32,
+}
+
+impl SessionSpeedLimits {
+    pub const FIELDS: &'static [&'static str] = &[
+        "speed-limit-down",
+        "speed-limit-down-enabled",
+        "speed-limit-up",
+        "speed-limit-up-enabled",
+        "alt-speed-enabled",
+        "alt-speed-down",
+        "alt-speed-up",
+    ];
+}
+
+/// Speed limits of a single torrent, in KB/s.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TorrentSpeedLimits {
+    pub download_limit: i32,
+    pub download_limited: bool,
+    pub upload_limit: i32,
+    pub upload_limited: bool,
+    pub honors_session_limits: bool,
+}
+
+impl TorrentSpeedLimits {
+    pub const FIELDS: &'static [&'static str] = &[
+        "downloadLimit",
+        "downloadLimited",
+        "uploadLimit",
+        "uploadLimited",
+        "honorsSessionLimits",
+    ];
+}
+
+/// Upload limit of a single torrent, in KB/s.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct TorrentUploadLimit {
+    pub id: i64,
+    pub upload_limit: i32,
+    pub upload_limited: bool,
+}
+
+impl TorrentUploadLimit {
+    pub const FIELDS: &'static [&'static str] = &["id", "uploadLimit", "uploadLimited"];
+
+    // Transmission has no switch for uploading, a limit of 0 is how it's paused
+    pub const fn is_upload_paused(&self) -> bool {
+        self.upload_limited && self.upload_limit == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitSource {
+    Global,
+    Torrent,
+    Alt,
+}
+
+impl LimitSource {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Torrent => "per-torrent",
+            Self::Alt => "alt",
+        }
+    }
+}
+
+/// The limits that actually bind a torrent, in KB/s, along with where they
+/// come from. `None` means unlimited.
+#[derive(Debug, Clone, Copy)]
+pub struct EffectiveLimits {
+    pub download: Option<(i32, LimitSource)>,
+    pub upload: Option<(i32, LimitSource)>,
+}
+
+impl EffectiveLimits {
+    pub fn new(session: &SessionSpeedLimits, torrent: &TorrentSpeedLimits) -> Self {
+        let session_limit = |limit, enabled, alt_limit| {
+            if !torrent.honors_session_limits {
+                None
+            } else if session.alt_speed_enabled {
+                Some((alt_limit, LimitSource::Alt))
+            } else if enabled {
+                Some((limit, LimitSource::Global))
+            } else {
+                None
+            }
+        };
+        let torrent_limit = |limit, enabled: bool| enabled.then_some((limit, LimitSource::Torrent));
+
+        Self {
+            download: tightest(
+                session_limit(
+                    session.speed_limit_down,
+                    session.speed_limit_down_enabled,
+                    session.alt_speed_down,
+                ),
+                torrent_limit(torrent.download_limit, torrent.download_limited),
+            ),
+            upload: tightest(
+                session_limit(
+                    session.speed_limit_up,
+                    session.speed_limit_up_enabled,
+                    session.alt_speed_up,
+                ),
+                torrent_limit(torrent.upload_limit, torrent.upload_limited),
+            ),
+        }
+    }
+}
+
+fn tightest(
+    session: Option<(i32, LimitSource)>,
+    torrent: Option<(i32, LimitSource)>,
+) -> Option<(i32, LimitSource)> {
+    match (session, torrent) {
+        (Some(session), Some(torrent)) => Some(if torrent.0 <= session.0 {
+            torrent
+        } else {
+            session
+        }),
+        (session, torrent) => session.or(torrent),
+    }
+}
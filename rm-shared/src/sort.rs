@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::header::Header;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    pub const fn arrow(self) -> &'static str {
+        match self {
+            Self::Ascending => "▲",
+            Self::Descending => "▼",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct SortKey {
+    pub header: Header,
+    pub direction: SortDirection,
+}
+
+impl SortKey {
+    pub const fn new(header: Header, direction: SortDirection) -> Self {
+        Self { header, direction }
+    }
+}
+
+pub struct SortPreset {
+    pub name: &'static str,
+    pub key: SortKey,
+}
+
+pub const SORT_PRESETS: [SortPreset; 4] = [
+    SortPreset {
+        name: "progress",
+        key: SortKey::new(Header::Progress, SortDirection::Descending),
+    },
+    SortPreset {
+        name: "download speed",
+        key: SortKey::new(Header::DownloadRate, SortDirection::Descending),
+    },
+    SortPreset {
+        name: "name",
+        key: SortKey::new(Header::Name, SortDirection::Ascending),
+    },
+    SortPreset {
+        name: "recently added",
+        key: SortKey::new(Header::AddedDate, SortDirection::Descending),
+    },
+];
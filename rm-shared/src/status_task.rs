@@ -3,6 +3,7 @@ use crate::utils::truncated_str;
 pub struct StatusTask {
     task_type: TaskType,
     what: String,
+    success_hint: Option<String>,
 }
 
 #[derive(Clone, Copy)]
@@ -11,6 +12,16 @@ enum TaskType {
     Delete,
     Move,
     Open,
+    Start,
+    StartNow,
+    Pause,
+    Verify,
+    Label,
+    Export,
+    Duplicate,
+    Sort,
+    Copy,
+    Queue,
 }
 
 impl StatusTask {
@@ -18,6 +29,7 @@ impl StatusTask {
         StatusTask {
             task_type: TaskType::Add,
             what: what.into(),
+            success_hint: None,
         }
     }
 
@@ -25,6 +37,7 @@ impl StatusTask {
         StatusTask {
             task_type: TaskType::Delete,
             what: what.into(),
+            success_hint: None,
         }
     }
 
@@ -32,6 +45,7 @@ impl StatusTask {
         StatusTask {
             task_type: TaskType::Move,
             what: what.into(),
+            success_hint: None,
         }
     }
 
@@ -39,17 +53,119 @@ impl StatusTask {
         StatusTask {
             task_type: TaskType::Open,
             what: what.into(),
+            success_hint: None,
         }
     }
 
+    pub fn new_start(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Start,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_start_now(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::StartNow,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_pause(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Pause,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_verify(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Verify,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_label(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Label,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_export(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Export,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_duplicate(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Duplicate,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_sort(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Sort,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_copy(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Copy,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    pub fn new_queue(what: impl Into<String>) -> Self {
+        StatusTask {
+            task_type: TaskType::Queue,
+            what: what.into(),
+            success_hint: None,
+        }
+    }
+
+    // Shown after the success message, e.g. to tell how the task can be undone
+    pub fn with_success_hint(mut self, hint: impl Into<String>) -> Self {
+        self.success_hint = Some(hint.into());
+        self
+    }
+
     pub fn success_str(&self) -> String {
         let truncated = truncated_str(&self.what, 60);
 
-        match self.task_type {
+        let success_str = match self.task_type {
             TaskType::Add => format!("Added {truncated}"),
             TaskType::Delete => format!("Deleted {truncated}"),
             TaskType::Move => format!("Moved {truncated}"),
             TaskType::Open => format!("Opened {truncated}"),
+            TaskType::Start => format!("Started {truncated}"),
+            TaskType::StartNow => format!("Force-started {truncated}"),
+            TaskType::Pause => format!("Paused {truncated}"),
+            TaskType::Verify => format!("Started verifying {truncated}"),
+            TaskType::Label => format!("Labeled {truncated}"),
+            TaskType::Export => format!("Exported to {truncated}"),
+            TaskType::Duplicate => format!("Already added: {truncated}"),
+            TaskType::Sort => format!("Sorted by {truncated}"),
+            TaskType::Copy => format!("Copied {truncated}"),
+            TaskType::Queue => format!("Queued {truncated}"),
+        };
+
+        match &self.success_hint {
+            Some(hint) => format!("{success_str} — {hint}"),
+            None => success_str,
         }
     }
 
@@ -61,6 +177,16 @@ impl StatusTask {
             TaskType::Delete => format!("Error deleting {truncated}"),
             TaskType::Move => format!("Error moving to {truncated}"),
             TaskType::Open => format!("Error opening {truncated}"),
+            TaskType::Start => format!("Error starting {truncated}"),
+            TaskType::StartNow => format!("Error force-starting {truncated}"),
+            TaskType::Pause => format!("Error pausing {truncated}"),
+            TaskType::Verify => format!("Error verifying {truncated}"),
+            TaskType::Label => format!("Error labeling {truncated}"),
+            TaskType::Export => format!("Error exporting to {truncated}"),
+            TaskType::Duplicate => format!("Error adding {truncated}"),
+            TaskType::Sort => format!("Error sorting by {truncated}"),
+            TaskType::Copy => format!("Error copying {truncated}"),
+            TaskType::Queue => format!("Error queuing {truncated}"),
         }
     }
 
@@ -72,6 +198,16 @@ impl StatusTask {
             TaskType::Delete => format!("Deleting {truncated}"),
             TaskType::Move => format!("Moving {truncated}"),
             TaskType::Open => format!("Opening {truncated}"),
+            TaskType::Start => format!("Starting {truncated}"),
+            TaskType::StartNow => format!("Force-starting {truncated}"),
+            TaskType::Pause => format!("Pausing {truncated}"),
+            TaskType::Verify => format!("Verifying {truncated}"),
+            TaskType::Label => format!("Labeling {truncated}"),
+            TaskType::Export => format!("Exporting to {truncated}"),
+            TaskType::Duplicate => format!("Adding {truncated}"),
+            TaskType::Sort => format!("Sorting by {truncated}"),
+            TaskType::Copy => format!("Copying {truncated}"),
+            TaskType::Queue => format!("Queuing {truncated}"),
         }
     }
 }
@@ -1,3 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+// How the torrent filter treats letter case
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CaseMatching {
+    // Case sensitive only if the pattern contains an uppercase letter
+    #[default]
+    Smart,
+    Ignore,
+    Respect,
+}
+
+impl CaseMatching {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Smart => Self::Ignore,
+            Self::Ignore => Self::Respect,
+            Self::Respect => Self::Smart,
+        }
+    }
+
+    pub const fn desc(self) -> &'static str {
+        match self {
+            Self::Smart => "smart case",
+            Self::Ignore => "ignore case",
+            Self::Respect => "match case",
+        }
+    }
+}
+
+// How torrent names that don't fit their column get shortened
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameTruncation {
+    // Cut off the end
+    #[default]
+    End,
+    // Keep both the beginning and the end, e.g. "Some.Long.Release…1080p.x265"
+    Middle,
+}
+
+// How many lines each torrent takes up in the list
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RowLayout {
+    // One line, with every column side by side
+    #[default]
+    Compact,
+    // The name on its own line, the other columns underneath it
+    Detailed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitSystem {
+    // KB, MB, GB... counted in 1024s, the way sizes were always shown
+    #[default]
+    Classic,
+    // KiB, MiB, GiB... (1 KiB = 1024 B)
+    Binary,
+    // kB, MB, GB... (1 kB = 1000 B)
+    Decimal,
+    // Same as Decimal, except speeds are shown in bits per second (kbps, Mbps...)
+    Bits,
+}
+
+impl UnitSystem {
+    pub fn format_size(self, bytes: i64) -> String {
+        match self {
+            Self::Classic => bytes_to_human_format(bytes),
+            Self::Binary => scaled_format(bytes as f64, 1024.0, &BINARY_UNITS),
+            Self::Decimal | Self::Bits => scaled_format(bytes as f64, 1000.0, &DECIMAL_UNITS),
+        }
+    }
+
+    pub fn format_speed(self, bytes_per_sec: i64) -> String {
+        match self {
+            Self::Classic | Self::Binary | Self::Decimal => self.format_size(bytes_per_sec),
+            Self::Bits => scaled_format(bytes_per_sec as f64 * 8.0, 1000.0, &BIT_UNITS),
+        }
+    }
+}
+
+const BINARY_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+const DECIMAL_UNITS: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+const BIT_UNITS: [&str; 5] = ["bps", "kbps", "Mbps", "Gbps", "Tbps"];
+
+fn scaled_format(value: f64, base: f64, units: &[&str; 5]) -> String {
+    if value == 0.0 {
+        return format!("0 {}", units[0]);
+    }
+
+    let mut value = value;
+    let mut unit_idx = 0;
+    // switch a bit earlier so that we don't end up with e.g. "1020.0 B"
+    while value >= base * 0.975 && unit_idx < units.len() - 1 {
+        value /= base;
+        unit_idx += 1;
+    }
+
+    if unit_idx == 0 {
+        format!("{value} {}", units[0])
+    } else {
+        format!("{value:.1} {}", units[unit_idx])
+    }
+}
+
 pub fn bytes_to_human_format(bytes: i64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
@@ -23,43 +127,65 @@ pub fn bytes_to_human_format(bytes: i64) -> String {
     format!("{value:.1} {unit}")
 }
 
+// Shows only the two most significant units, e.g. "3d 4h" or "5m 12s"
 pub fn seconds_to_human_format(seconds: i64) -> String {
     const MINUTE: i64 = 60;
     const HOUR: i64 = MINUTE * 60;
     const DAY: i64 = HOUR * 24;
 
-    if seconds == 0 {
+    if seconds <= 0 {
         return "0s".to_string();
     }
 
-    let mut curr_string = String::new();
+    let parts = [
+        (seconds / DAY, "d"),
+        (seconds % DAY / HOUR, "h"),
+        (seconds % HOUR / MINUTE, "m"),
+        (seconds % MINUTE, "s"),
+    ];
 
-    let mut rest = seconds;
-    if seconds > DAY {
-        let days = rest / DAY;
-        rest %= DAY;
+    let first = parts
+        .iter()
+        .position(|(value, _)| *value > 0)
+        .expect("seconds are positive");
 
-        curr_string = format!("{curr_string}{days}d");
-    }
+    parts[first..]
+        .iter()
+        .take(2)
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
-    if seconds > HOUR {
-        let hours = rest / HOUR;
-        rest %= HOUR;
-        curr_string = format!("{curr_string}{hours}h");
-        // skip minutes & seconds for multi-day durations
-        if seconds > DAY {
-            return curr_string;
-        }
+// Transmission reports -1 when the ETA doesn't apply (e.g. the torrent isn't
+// downloading) and -2 when it can't be estimated.
+pub fn eta_format(eta_secs: i64, is_stalled: bool) -> String {
+    if is_stalled {
+        return "stalled".to_string();
     }
 
-    if seconds > MINUTE {
-        let minutes = rest / MINUTE;
-        rest %= MINUTE;
-        curr_string = format!("{curr_string}{minutes}m");
+    match eta_secs {
+        -2 => "∞".to_string(),
+        ..0 => String::default(),
+        eta_secs => seconds_to_human_format(eta_secs),
     }
+}
 
-    curr_string = format!("{curr_string}{rest}s");
-    curr_string
+pub fn seconds_to_relative_format(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = MINUTE * 60;
+    const DAY: i64 = HOUR * 24;
+
+    if seconds < MINUTE {
+        "just now".to_string()
+    } else if seconds < HOUR {
+        format!("{}m ago", seconds / MINUTE)
+    } else if seconds < DAY {
+        format!("{}h ago", seconds / HOUR)
+    } else {
+        format!("{}d ago", seconds / DAY)
+    }
 }
 
 pub fn download_speed_format(download_speed: &str) -> String {
@@ -76,6 +202,34 @@ pub fn upload_speed_format(upload_speed: &str) -> String {
     upload_speed.to_string()
 }
 
+// Replaces the middle of `str` with "…" so that it's at most `max` chars long.
+pub fn middle_truncated_str(str: &str, max: usize) -> String {
+    let len = str.chars().count();
+    let Some((head_len, tail_len)) = middle_truncation(len, max) else {
+        return str.to_string();
+    };
+    if max == 0 {
+        return String::new();
+    }
+
+    let head: String = str.chars().take(head_len).collect();
+    let tail: String = str.chars().skip(len - tail_len).collect();
+    format!("{head}…{tail}")
+}
+
+// How many chars from the start and from the end of a string `len` chars long are kept
+// when it's truncated in the middle to fit in `max` chars, or None if it already fits
+pub fn middle_truncation(len: usize, max: usize) -> Option<(usize, usize)> {
+    if len <= max {
+        return None;
+    }
+
+    // The tail usually tells torrents apart (episode, quality...), so it gets the extra char
+    let kept = max.saturating_sub(1);
+    let head_len = kept / 2;
+    Some((head_len, kept - head_len))
+}
+
 pub fn truncated_str(str: &str, max: usize) -> String {
     if str.chars().count() < max {
         str.to_string()